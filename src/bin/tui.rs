@@ -1,6 +1,37 @@
-use solitaire_rs::{infrastructure::Container, ui::termui};
+use std::{env, process};
+
+use solitaire_rs::{
+    infrastructure::{parse_args, Container, Invocation, USAGE},
+    ui::termui,
+};
 
 fn main() {
-    let container = Container::default();
-    termui::run(&container).unwrap();
+    let config = match parse_args(env::args().skip(1)) {
+        Ok(Invocation::Run(config)) => config,
+        Ok(Invocation::Help) => {
+            println!("{USAGE}");
+            return;
+        }
+        Ok(Invocation::Version) => {
+            println!("solitaire-rs {}", env!("CARGO_PKG_VERSION"));
+            return;
+        }
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    let container = match Container::new(config) {
+        Ok(container) => container,
+        Err(err) => {
+            eprintln!("error: {err}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(err) = termui::run(&container) {
+        eprintln!("error: {err}");
+        process::exit(1);
+    }
 }
@@ -0,0 +1,93 @@
+use std::time::{Duration, Instant};
+
+/**
+ * Measure the time spent on a game, including time spent in previous sessions.
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Clock {
+    elapsed: Duration,
+    // When the clock was last started, unless it is stopped.
+    started_at: Option<Instant>,
+}
+
+impl Clock {
+    /**
+     * A stopped clock which already measured `elapsed`.
+     */
+    pub fn new(elapsed: Duration) -> Self {
+        Self {
+            elapsed,
+            started_at: None,
+        }
+    }
+
+    pub fn get_elapsed(&self) -> Duration {
+        match self.started_at {
+            Some(started_at) => self.elapsed + started_at.elapsed(),
+            None => self.elapsed,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    /**
+     * Start or keep measuring time.
+     */
+    pub fn start(&mut self) {
+        if self.started_at.is_none() {
+            self.started_at = Some(Instant::now());
+        }
+    }
+
+    /**
+     * Stop measuring time, e.g. while the game is paused or once it is over.
+     */
+    pub fn stop(&mut self) {
+        self.elapsed = self.get_elapsed();
+        self.started_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread::sleep;
+
+    use super::*;
+
+    const TICK: Duration = Duration::from_millis(10);
+
+    #[test]
+    fn only_measures_time_while_running() {
+        let mut clock = Clock::new(Duration::from_secs(60));
+        sleep(TICK);
+
+        assert!(!clock.is_running());
+        assert_eq!(clock.get_elapsed(), Duration::from_secs(60));
+
+        clock.start();
+        sleep(TICK);
+
+        assert!(clock.is_running());
+        assert!(clock.get_elapsed() >= Duration::from_secs(60) + TICK);
+
+        clock.stop();
+        let elapsed = clock.get_elapsed();
+        sleep(TICK);
+
+        assert!(!clock.is_running());
+        assert_eq!(clock.get_elapsed(), elapsed);
+    }
+
+    #[test]
+    fn keeps_running_when_started_again() {
+        let mut clock = Clock::default();
+
+        clock.start();
+        sleep(TICK);
+        clock.start();
+
+        assert!(clock.get_elapsed() >= TICK);
+    }
+}
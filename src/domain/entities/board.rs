@@ -1,30 +1,115 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
+    fmt,
+    hash::{Hash, Hasher},
+    mem::discriminant,
+    str::FromStr,
+};
+
 use rand::prelude::*;
 
-use super::{get_standard_pack, Card};
+use super::{
+    get_standard_pack, Card, Command, DealRng, GameOutcome, History, InvalidTarget, Move,
+    MoveError, Passes, PositionError, Rank, Reveal, Rules, Suit,
+};
+
+/**
+ * Random deals are picked among this many deal numbers, so that they are easy to share.
+ */
+pub const NUM_RANDOM_DEALS: u64 = 1_000_000;
 
 pub type Pile = Vec<Card>;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Target {
     Stock,
+    Waste,
     Pile(usize),
+    Foundation(usize),
+}
+
+/**
+ * Targets are written `S` and `W` for the stock and the waste, `F1` to `F4`
+ * for foundations, and `1` to `7` for tableau piles.
+ */
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::Stock => write!(f, "S"),
+            Target::Waste => write!(f, "W"),
+            Target::Pile(index) => write!(f, "{}", index + 1),
+            Target::Foundation(index) => write!(f, "F{}", index + 1),
+        }
+    }
+}
+
+impl FromStr for Target {
+    type Err = InvalidTarget;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidTarget(value.to_string());
+
+        // Numbers must be written as they are displayed, e.g. not `03` or `+3`.
+        let index = |number: &str| match number.parse::<usize>() {
+            Ok(n) if n > 0 && n.to_string() == number => Ok(n - 1),
+            _ => Err(invalid()),
+        };
+
+        match value {
+            "S" => Ok(Target::Stock),
+            "W" => Ok(Target::Waste),
+            _ => match value.strip_prefix('F') {
+                Some(number) => index(number).map(Target::Foundation),
+                None => index(value).map(Target::Pile),
+            },
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Board {
     tableau: Vec<Pile>,
     foundations: Vec<Pile>,
     stock: Pile,
     waste: Pile,
+    rules: Rules,
+    // Number of the current pass through the stock, starting at 1.
+    pass: u32,
+    history: History,
+    deal_number: Option<u64>,
 }
 
 impl Default for Board {
     fn default() -> Self {
+        Self::new(Rules::default())
+    }
+}
+
+impl Board {
+    /**
+     * Start a random deal.
+     */
+    pub fn new(rules: Rules) -> Self {
+        let deal_number = rand::thread_rng().gen_range(1..=NUM_RANDOM_DEALS);
+        Self::deal(deal_number, rules)
+    }
+
+    /**
+     * Start the deal with the given number, using default rules.
+     */
+    pub fn from_seed(seed: u64) -> Self {
+        Self::deal(seed, Rules::default())
+    }
+
+    /**
+     * Start the deal with the given number. The same number always leads to the same deal.
+     */
+    pub fn deal(deal_number: u64, rules: Rules) -> Self {
         let mut tableau = (0..7).map(|_| Pile::new()).collect::<Vec<_>>();
 
         let mut pack = get_standard_pack();
 
-        pack.shuffle(&mut rand::thread_rng());
+        DealRng::new(deal_number).shuffle(&mut pack);
 
         for index in 0..7 {
             let pile = &mut tableau[index];
@@ -32,8 +117,7 @@ impl Default for Board {
             card.reveal();
             pile.push(card);
 
-            for j in index + 1..7 {
-                let other_pile = &mut tableau[j];
+            for other_pile in tableau.iter_mut().skip(index + 1) {
                 other_pile.push(pack.pop().unwrap());
             }
         }
@@ -51,11 +135,176 @@ impl Default for Board {
             foundations,
             stock,
             waste: Pile::new(),
+            rules,
+            pass: 1,
+            history: History::default(),
+            deal_number: Some(deal_number),
+        }
+    }
+
+    /**
+     * Set up a position from its piles, e.g. to resume a saved game.
+     *
+     * The piles must hold each card of the pack exactly once, and the foundations
+     * must be built up as they would be in play.
+     */
+    pub fn from_piles(
+        deal_number: Option<u64>,
+        rules: Rules,
+        tableau: Vec<Pile>,
+        foundations: Vec<Pile>,
+        stock: Pile,
+        waste: Pile,
+    ) -> Result<Self, PositionError> {
+        if tableau.len() != 7 || foundations.len() != 4 {
+            return Err(PositionError::WrongNumberOfPiles);
+        }
+
+        let board = Self {
+            tableau,
+            foundations,
+            stock,
+            waste,
+            rules,
+            pass: 1,
+            history: History::default(),
+            deal_number,
+        };
+
+        board.check_cards()?;
+        board.check_foundations()?;
+
+        Ok(board)
+    }
+
+    fn check_cards(&self) -> Result<(), PositionError> {
+        let mut seen = HashSet::new();
+
+        let piles = self
+            .tableau
+            .iter()
+            .chain(&self.foundations)
+            .chain([&self.stock, &self.waste]);
+
+        for card in piles.flatten() {
+            // Cards are the same whether face up or not.
+            let mut card = *card;
+            card.reveal();
+
+            if !seen.insert(card) {
+                return Err(PositionError::DuplicateCard(card));
+            }
+        }
+
+        let mut pack = get_standard_pack();
+        pack.iter_mut().for_each(Card::reveal);
+
+        match pack.into_iter().find(|c| !seen.contains(c)) {
+            Some(card) => Err(PositionError::MissingCard(card)),
+            None => Ok(()),
+        }
+    }
+
+    fn check_foundations(&self) -> Result<(), PositionError> {
+        for foundation in &self.foundations {
+            for (index, card) in foundation.iter().enumerate() {
+                let below = index.checked_sub(1).map(|below| &foundation[below]);
+
+                if !card.is_visible() || check_founded_on(card, below).is_err() {
+                    return Err(PositionError::MisplacedOnFoundation(*card));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Restore how the position was reached: the current pass through
+     * the stock, and moves that can be undone or redone.
+     */
+    pub fn restore_history(&mut self, pass: u32, history: History) -> Result<(), PositionError> {
+        let max_pass = match self.rules.passes {
+            Passes::Unlimited => u32::MAX,
+            Passes::Limited(max) => max,
+        };
+
+        if pass == 0 || pass > max_pass {
+            return Err(PositionError::InvalidPass);
+        }
+
+        self.pass = pass;
+        self.history = history;
+
+        self.check_history()
+    }
+
+    /**
+     * Check that all moves of the history can be undone and redone, so that doing so can't panic.
+     */
+    fn check_history(&self) -> Result<(), PositionError> {
+        let mut board = self.clone();
+
+        for commands in self.history.iter_undone().rev() {
+            for &command in commands {
+                if !board.can_execute(command) {
+                    return Err(PositionError::InvalidHistory);
+                }
+                board.execute(command);
+            }
+        }
+
+        let mut board = self.clone();
+
+        for commands in self.history.iter().rev() {
+            for &command in commands.iter().rev() {
+                if !board.can_revert(command) {
+                    return Err(PositionError::InvalidHistory);
+                }
+                board.revert(command);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn can_execute(&self, command: Command) -> bool {
+        match command {
+            Command::Transfer {
+                source,
+                dest,
+                num_cards,
+            } => self.can_transfer(source, dest, num_cards),
+            Command::Deal { num_cards } => num_cards <= self.stock.len(),
+            Command::Reload => self.stock.is_empty(),
+            Command::Reveal { target } => {
+                matches!(self.get(target), Some(pile) if !pile.is_empty())
+            }
+        }
+    }
+
+    fn can_revert(&self, command: Command) -> bool {
+        match command {
+            Command::Transfer {
+                source,
+                dest,
+                num_cards,
+            } => self.can_transfer(dest, source, num_cards),
+            Command::Deal { num_cards } => num_cards <= self.waste.len(),
+            Command::Reload => self.waste.is_empty() && self.pass > 1,
+            Command::Reveal { target } => {
+                matches!(self.get(target), Some(pile) if !pile.is_empty())
+            }
+        }
+    }
+
+    fn can_transfer(&self, source: Target, dest: Target, num_cards: usize) -> bool {
+        match (self.get(source), self.get(dest)) {
+            (Some(pile), Some(_)) => source != dest && num_cards <= pile.len(),
+            _ => false,
         }
     }
-}
 
-impl Board {
     pub fn get_stock(&self) -> &Pile {
         &self.stock
     }
@@ -72,57 +321,322 @@ impl Board {
         &self.tableau
     }
 
+    /**
+     * Number of the deal this game started from, if it wasn't set up from a given position.
+     */
+    pub fn get_deal_number(&self) -> Option<u64> {
+        self.deal_number
+    }
+
+    pub fn get_rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /**
+     * Number of the current pass through the stock, starting at 1.
+     */
+    pub fn get_pass(&self) -> u32 {
+        self.pass
+    }
+
+    /**
+     * Number of times the stock can still be reloaded, or `None` if unlimited.
+     */
+    pub fn get_remaining_passes(&self) -> Option<u32> {
+        match self.rules.passes {
+            Passes::Unlimited => None,
+            Passes::Limited(max) => Some(max.saturating_sub(self.pass)),
+        }
+    }
+
     pub fn get(&self, target: Target) -> Option<&Pile> {
         match target {
             Target::Stock => Some(&self.stock),
+            Target::Waste => Some(&self.waste),
             Target::Pile(index) => self.tableau.get(index),
+            Target::Foundation(index) => self.foundations.get(index),
         }
     }
 
-    pub fn get_mut(&mut self, target: Target) -> Option<&mut Pile> {
+    fn get_mut(&mut self, target: Target) -> Option<&mut Pile> {
         match target {
             Target::Stock => Some(&mut self.stock),
+            Target::Waste => Some(&mut self.waste),
             Target::Pile(index) => self.tableau.get_mut(index),
+            Target::Foundation(index) => self.foundations.get_mut(index),
         }
     }
 
     pub fn get_previous_target(&self, target: Target) -> Target {
         match target {
-            Target::Stock => Target::Pile(self.tableau.len() - 1),
-            Target::Pile(0) => Target::Stock,
+            Target::Stock => Target::Foundation(self.foundations.len() - 1),
+            Target::Waste => Target::Stock,
+            Target::Pile(0) => Target::Waste,
             Target::Pile(n) => Target::Pile(n - 1),
+            Target::Foundation(0) => Target::Pile(self.tableau.len() - 1),
+            Target::Foundation(n) => Target::Foundation(n - 1),
         }
     }
 
     pub fn get_next_target(&self, target: Target) -> Target {
         match target {
-            Target::Stock => Target::Pile(0),
-            Target::Pile(n) if n == self.tableau.len() - 1 => Target::Stock,
+            Target::Stock => Target::Waste,
+            Target::Waste => Target::Pile(0),
+            Target::Pile(n) if n == self.tableau.len() - 1 => Target::Foundation(0),
             Target::Pile(n) => Target::Pile(n + 1),
+            Target::Foundation(n) if n == self.foundations.len() - 1 => Target::Stock,
+            Target::Foundation(n) => Target::Foundation(n + 1),
         }
     }
 
-    pub fn maybe_transfer(&mut self, source: Target, dest: Target, num_cards: usize) {
-        if dest == Target::Stock {
-            // Can't transfer to the stock pile.
-            return;
+    pub fn get_outcome(&self) -> GameOutcome {
+        if self.is_won() {
+            GameOutcome::Won
+        } else if !self.has_any_move() {
+            GameOutcome::Stuck
+        } else {
+            GameOutcome::InProgress
         }
+    }
 
-        let source_pile = self.get(source).unwrap();
-        let dest_pile = self.get(dest).unwrap();
+    /**
+     * Whether every card of the pack is on the foundations.
+     */
+    pub fn is_won(&self) -> bool {
+        let num_founded = self.foundations.iter().map(|f| f.len()).sum::<usize>();
+
+        num_founded == get_standard_pack().len()
+    }
 
-        // Card of rank N can be transferred to an empty pile,
-        // or a pile whose top card is hidden...
-        if dest_pile.last().map_or(true, |c| !c.is_visible()) {
-            self.transfer(source, dest, num_cards);
-            return;
+    fn has_any_move(&self) -> bool {
+        self.legal_moves().next().is_some()
+    }
+
+    /**
+     * Iterate over all moves that can be played from the current position.
+     *
+     * Each of them is guaranteed to succeed when passed to `Board::apply()`.
+     */
+    pub fn legal_moves(&self) -> impl Iterator<Item = Move> + '_ {
+        let sources = std::iter::once(Target::Waste)
+            .chain((0..self.tableau.len()).map(Target::Pile))
+            .chain((0..self.foundations.len()).map(Target::Foundation));
+
+        let transfers = sources.flat_map(move |source| {
+            let num_visible = self
+                .get(source)
+                .unwrap()
+                .iter()
+                .filter(|c| c.is_visible())
+                .count();
+
+            // Only runs of tableau cards can be moved together.
+            let max_cards = match source {
+                Target::Pile(_) => num_visible,
+                _ => num_visible.min(1),
+            };
+
+            (1..=max_cards).flat_map(move |num_cards| {
+                self.get_build_targets().map(move |dest| Move::Transfer {
+                    source,
+                    dest,
+                    num_cards,
+                })
+            })
+        });
+
+        let reveals = (0..self.tableau.len()).map(|index| Move::Reveal {
+            target: Target::Pile(index),
+        });
+
+        [Move::Deal, Move::Reload]
+            .into_iter()
+            .chain(reveals)
+            .chain(transfers)
+            .filter(move |&mv| self.check_move(mv).is_ok())
+    }
+
+    /**
+     * Iterate over the legal moves, listing only one of the moves taking the same cards
+     * to different empty piles, since they all lead to equivalent positions.
+     */
+    pub fn distinct_moves(&self) -> impl Iterator<Item = Move> + '_ {
+        let mut seen = HashSet::new();
+
+        self.legal_moves().filter(move |mv| {
+            let dest = mv.get_dest();
+            !self.get(dest).unwrap().is_empty()
+                || seen.insert((mv.get_source(), mv.get_num_cards(), discriminant(&dest)))
+        })
+    }
+
+    /**
+     * The card left on top of the source pile once the move is played, if any.
+     */
+    pub fn get_card_left_by(&self, mv: Move) -> Option<&Card> {
+        let pile = self.get(mv.get_source())?;
+        let len = pile.len().checked_sub(mv.get_num_cards())?;
+        pile[..len].last()
+    }
+
+    /**
+     * Whether the move leaves a face down card on top of its source pile, ready to be turned over.
+     */
+    pub fn uncovers_hidden_card(&self, mv: Move) -> bool {
+        matches!(self.get_card_left_by(mv), Some(card) if !card.is_visible())
+    }
+
+    /**
+     * Identify a position regardless of the order of tableau piles and foundations,
+     * e.g. to avoid exploring the same position twice when searching for moves.
+     */
+    pub fn get_position_key(&self) -> u64 {
+        let mut piles = self
+            .tableau
+            .iter()
+            .map(|pile| {
+                let mut hasher = DefaultHasher::new();
+                pile.hash(&mut hasher);
+                hasher.finish()
+            })
+            .collect::<Vec<_>>();
+        piles.sort_unstable();
+
+        let mut foundations = self
+            .foundations
+            .iter()
+            .filter_map(|f| f.last())
+            .map(|top| (top.suit as u8, top.rank.0))
+            .collect::<Vec<_>>();
+        foundations.sort_unstable();
+
+        let mut hasher = DefaultHasher::new();
+        piles.hash(&mut hasher);
+        foundations.hash(&mut hasher);
+        self.stock.hash(&mut hasher);
+        self.waste.hash(&mut hasher);
+
+        // With unlimited passes, going through the stock again leads to the same position.
+        if let Passes::Limited(_) = self.rules.passes {
+            self.get_remaining_passes().hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    fn get_build_targets(&self) -> impl Iterator<Item = Target> {
+        (0..self.tableau.len())
+            .map(Target::Pile)
+            .chain((0..self.foundations.len()).map(Target::Foundation))
+    }
+
+    pub fn check_move(&self, mv: Move) -> Result<(), MoveError> {
+        match mv {
+            Move::Transfer {
+                source,
+                dest,
+                num_cards,
+            } => self.check_transfer(source, dest, num_cards),
+            Move::Deal => self.check_deal(),
+            Move::Reload => self.check_reload(),
+            Move::Reveal { target } => self.check_reveal(target),
+        }
+    }
+
+    pub fn apply(&mut self, mv: Move) -> Result<(), MoveError> {
+        match mv {
+            Move::Transfer {
+                source,
+                dest,
+                num_cards,
+            } => self.maybe_transfer(source, dest, num_cards),
+            Move::Deal => self.deal_from_stock(),
+            Move::Reload => self.reload_stock(),
+            Move::Reveal { target } => self.reveal(target),
+        }
+    }
+
+    fn get_pile(&self, target: Target) -> Result<&Pile, MoveError> {
+        self.get(target).ok_or(MoveError::NoSuchPile)
+    }
+
+    pub fn maybe_transfer(
+        &mut self,
+        source: Target,
+        dest: Target,
+        num_cards: usize,
+    ) -> Result<(), MoveError> {
+        self.check_transfer(source, dest, num_cards)?;
+        self.play(Command::Transfer {
+            source,
+            dest,
+            num_cards,
+        });
+        self.maybe_auto_reveal(source);
+        Ok(())
+    }
+
+    fn check_transfer(
+        &self,
+        source: Target,
+        dest: Target,
+        num_cards: usize,
+    ) -> Result<(), MoveError> {
+        let source_pile = self.get_pile(source)?;
+        let dest_pile = self.get_pile(dest)?;
+
+        if matches!(dest, Target::Stock | Target::Waste) || dest == source {
+            return Err(MoveError::IllegalDestination);
+        }
+
+        if matches!(
+            (source, dest),
+            (Target::Foundation(_), Target::Foundation(_))
+        ) {
+            return Err(MoveError::IllegalDestination);
+        }
+
+        if source_pile.is_empty() {
+            return Err(MoveError::EmptySource);
+        }
+
+        if num_cards == 0 || num_cards > source_pile.len() {
+            return Err(MoveError::NotEnoughCards);
+        }
+
+        // Only the top card of the waste or of a foundation can be played.
+        if matches!(source, Target::Waste | Target::Foundation(_)) && num_cards > 1 {
+            return Err(MoveError::NotEnoughCards);
         }
 
-        // ... or a pile whose top card has rank N + 1.
-        let source_rank = source_pile[source_pile.len() - num_cards].rank.0;
-        let dest_rank = dest_pile.last().unwrap().rank.0;
-        if dest_rank == source_rank + 1 {
-            self.transfer(source, dest, num_cards);
+        // The moved cards must be face up and already form a valid run,
+        // i.e. descending ranks in alternating colors.
+        let run = &source_pile[source_pile.len() - num_cards..];
+
+        if !run.iter().all(|c| c.is_visible()) {
+            return Err(MoveError::HiddenCard);
+        }
+
+        for pair in run.windows(2) {
+            check_built_on(&pair[1], &pair[0])?;
+        }
+
+        if let Target::Foundation(_) = dest {
+            // Foundations are built one card at a time.
+            if num_cards > 1 {
+                return Err(MoveError::NotEnoughCards);
+            }
+
+            return check_founded_on(&run[0], dest_pile.last());
+        }
+
+        match dest_pile.last() {
+            // Only a king can be moved to an empty pile...
+            None if run[0].rank == Rank::KING => Ok(()),
+            None => Err(MoveError::RankMismatch),
+            // ... otherwise the run must continue the destination pile.
+            Some(top_card) if !top_card.is_visible() => Err(MoveError::HiddenCard),
+            Some(top_card) => check_built_on(&run[0], top_card),
         }
     }
 
@@ -137,69 +651,875 @@ impl Board {
         dest_pile.extend(items);
     }
 
-    pub fn maybe_move_to_a_foundation(&mut self, target: Target) -> bool {
-        let pile = self.get(target).unwrap();
+    pub fn maybe_move_to_a_foundation(&mut self, target: Target) -> Result<(), MoveError> {
+        let dest = self.find_foundation_for(target)?;
+
+        self.play(Command::Transfer {
+            source: target,
+            dest,
+            num_cards: 1,
+        });
+        self.maybe_auto_reveal(target);
+
+        Ok(())
+    }
 
-        if pile.is_empty() {
-            return false;
+    /**
+     * Find a foundation where the top card of the pile can be transferred, if any.
+     * We do this automatically for better UX.
+     */
+    fn find_foundation_for(&self, target: Target) -> Result<Target, MoveError> {
+        if let Target::Foundation(_) = target {
+            return Err(MoveError::IllegalDestination);
         }
 
-        let card = *pile.last().unwrap();
+        let pile = self.get_pile(target)?;
+
+        let card = pile.last().ok_or(MoveError::EmptySource)?;
 
         if !card.is_visible() {
-            return false;
+            return Err(MoveError::HiddenCard);
         }
 
-        let mut was_transferred = false;
+        (0..self.foundations.len())
+            .map(Target::Foundation)
+            .find(|&dest| self.check_transfer(target, dest, 1).is_ok())
+            .ok_or(MoveError::IllegalDestination)
+    }
+
+    /**
+     * Whether the card could go on top of one of the foundations.
+     */
+    pub fn can_found(&self, card: &Card) -> bool {
+        self.foundations
+            .iter()
+            .any(|foundation| check_founded_on(card, foundation.last()).is_ok())
+    }
 
-        for foundation in self.foundations.iter_mut() {
-            // Find a foundation where the card be transferred, if any.
-            // We do this automatically for better UX.
+    /**
+     * Move cards to the foundations as long as it is safe to do so, as part of the last move,
+     * so that undoing it takes them back as well. Returns how many cards were moved.
+     *
+     * Aces and twos are always safe to move, and so is any card once both cards
+     * of the opposite color and the rank below are on the foundations, since
+     * nothing could be built on it anymore.
+     */
+    pub fn autoplay(&mut self) -> usize {
+        if !self.history.can_undo() {
+            return 0;
+        }
 
-            // Empty foundations can only be transferred an ace.
-            if foundation.is_empty() {
-                if card.rank.0 == 1 {
-                    foundation.push(card);
-                    was_transferred = true;
-                    break;
+        let mut num_moved = 0;
+
+        loop {
+            let sources =
+                std::iter::once(Target::Waste).chain((0..self.tableau.len()).map(Target::Pile));
+
+            let next = sources
+                .filter(|&source| self.is_safe_to_found(source))
+                .find_map(|source| Some((source, self.find_foundation_for(source).ok()?)));
+
+            let (source, dest) = match next {
+                Some(next) => next,
+                None => return num_moved,
+            };
+
+            self.play_along(Command::Transfer {
+                source,
+                dest,
+                num_cards: 1,
+            });
+            self.maybe_auto_reveal(source);
+            num_moved += 1;
+        }
+    }
+
+    /**
+     * Turn over the card left on top of the pile as part of the last move, if the rules say so.
+     */
+    fn maybe_auto_reveal(&mut self, target: Target) {
+        if self.rules.reveal == Reveal::Auto && self.check_reveal(target).is_ok() {
+            self.play_along(Command::Reveal { target });
+        }
+    }
+
+    /**
+     * Whether the top card of the pile can go to the foundations without ever being missed
+     * on the tableau.
+     */
+    pub fn is_safe_to_found(&self, target: Target) -> bool {
+        let card = match self.get(target).and_then(|pile| pile.last()) {
+            Some(card) => *card,
+            None => return false,
+        };
+
+        let Rank(rank) = card.rank;
+
+        rank <= 2
+            || Suit::all()
+                .into_iter()
+                .filter(|suit| suit.color() != card.suit.color())
+                .all(|suit| self.get_founded_rank(suit) >= rank - 1)
+    }
+
+    /**
+     * Rank of the highest card of the suit on the foundations, or 0 if there is none.
+     */
+    fn get_founded_rank(&self, suit: Suit) -> u8 {
+        self.foundations
+            .iter()
+            .find(|foundation| foundation.first().map(|card| card.suit) == Some(suit))
+            .and_then(|foundation| foundation.last())
+            .map_or(0, |card| card.rank.0)
+    }
+
+    /**
+     * Whether the stock and waste are empty and every card of the tableau is face up,
+     * so that finishing the game takes no more thinking.
+     */
+    pub fn can_auto_complete(&self) -> bool {
+        self.stock.is_empty()
+            && self.waste.is_empty()
+            && self.tableau.iter().flatten().all(|card| card.is_visible())
+            && self.get_outcome() != GameOutcome::Won
+    }
+
+    /**
+     * Move one card to the foundations while auto-completing, lowest ranks first.
+     */
+    pub fn auto_complete_step(&mut self) -> Result<(), MoveError> {
+        if !self.can_auto_complete() {
+            return Err(MoveError::CantAutoComplete);
+        }
+
+        let target = (0..self.tableau.len())
+            .map(Target::Pile)
+            .filter(|&target| {
+                (0..self.foundations.len()).any(|index| {
+                    self.check_transfer(target, Target::Foundation(index), 1)
+                        .is_ok()
+                })
+            })
+            .min_by_key(|&target| self.get(target).unwrap().last().unwrap().rank.0)
+            .ok_or(MoveError::NoMoveAvailable)?;
+
+        self.maybe_move_to_a_foundation(target)
+    }
+
+    /**
+     * Turn over one or three cards from the stock onto the waste, depending on the draw mode.
+     */
+    pub fn deal_from_stock(&mut self) -> Result<(), MoveError> {
+        self.check_deal()?;
+
+        let num_cards = self.rules.draw_mode.num_cards().min(self.stock.len());
+        self.play(Command::Deal { num_cards });
+
+        Ok(())
+    }
+
+    fn check_deal(&self) -> Result<(), MoveError> {
+        if self.stock.is_empty() {
+            return Err(MoveError::EmptySource);
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Turn over the top card of a tableau pile.
+     */
+    pub fn reveal(&mut self, target: Target) -> Result<(), MoveError> {
+        self.check_reveal(target)?;
+        self.play(Command::Reveal { target });
+        Ok(())
+    }
+
+    fn check_reveal(&self, target: Target) -> Result<(), MoveError> {
+        let top_card = self
+            .get_pile(target)?
+            .last()
+            .ok_or(MoveError::EmptySource)?;
+
+        if !matches!(target, Target::Pile(_)) {
+            return Err(MoveError::IllegalDestination);
+        }
+
+        if top_card.is_visible() {
+            return Err(MoveError::AlreadyRevealed);
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Turn the waste back over into the stock, so that cards are dealt again in the same order.
+     */
+    pub fn reload_stock(&mut self) -> Result<(), MoveError> {
+        self.check_reload()?;
+        self.play(Command::Reload);
+        Ok(())
+    }
+
+    fn check_reload(&self) -> Result<(), MoveError> {
+        if !self.stock.is_empty() {
+            return Err(MoveError::StockNotEmpty);
+        }
+
+        if self.waste.is_empty() {
+            return Err(MoveError::EmptySource);
+        }
+
+        if self.get_remaining_passes() == Some(0) {
+            return Err(MoveError::NoPassesLeft);
+        }
+
+        Ok(())
+    }
+
+    pub fn get_history(&self) -> &History {
+        &self.history
+    }
+
+    /**
+     * The position this game started from, before any move was played.
+     */
+    pub fn get_initial_position(&self) -> Board {
+        let mut board = self.clone();
+        while board.undo().is_ok() {}
+        board.history = History::default();
+        board
+    }
+
+    /**
+     * Take back the last move.
+     */
+    pub fn undo(&mut self) -> Result<(), MoveError> {
+        let commands = self.history.undo().ok_or(MoveError::NothingToUndo)?;
+
+        for command in commands.into_iter().rev() {
+            self.revert(command);
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Play again the last move that was undone.
+     */
+    pub fn redo(&mut self) -> Result<(), MoveError> {
+        let commands = self.history.redo().ok_or(MoveError::NothingToRedo)?;
+
+        for command in commands {
+            self.execute(command);
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Execute a validated command, recording it as a new move.
+     */
+    fn play(&mut self, command: Command) {
+        self.execute(command);
+        self.history.record(vec![command]);
+    }
+
+    /**
+     * Execute a validated command as part of the last move.
+     */
+    fn play_along(&mut self, command: Command) {
+        self.execute(command);
+        self.history.append(command);
+    }
+
+    fn execute(&mut self, command: Command) {
+        match command {
+            Command::Transfer {
+                source,
+                dest,
+                num_cards,
+            } => self.transfer(source, dest, num_cards),
+            Command::Deal { num_cards } => {
+                for _ in 0..num_cards {
+                    let mut card = self.stock.pop().unwrap();
+                    card.reveal();
+                    self.waste.push(card);
                 }
-                continue;
             }
+            Command::Reload => {
+                while let Some(mut card) = self.waste.pop() {
+                    card.hide();
+                    self.stock.push(card);
+                }
+                self.pass += 1;
+            }
+            Command::Reveal { target } => {
+                self.get_mut(target).unwrap().last_mut().unwrap().reveal();
+            }
+        }
+    }
 
-            // For established foundations, the suit must match and
-            // cards must be stacked with ranks ascending.
-            let last = foundation.last().unwrap();
-
-            if last.suit == card.suit && card.rank.0 == last.rank.0 + 1 {
-                foundation.push(card);
-                was_transferred = true;
-                break;
+    fn revert(&mut self, command: Command) {
+        match command {
+            Command::Transfer {
+                source,
+                dest,
+                num_cards,
+            } => self.transfer(dest, source, num_cards),
+            Command::Deal { num_cards } => {
+                for _ in 0..num_cards {
+                    let mut card = self.waste.pop().unwrap();
+                    card.hide();
+                    self.stock.push(card);
+                }
+            }
+            Command::Reload => {
+                // The stock was empty before reloading.
+                while let Some(mut card) = self.stock.pop() {
+                    card.reveal();
+                    self.waste.push(card);
+                }
+                self.pass -= 1;
+            }
+            Command::Reveal { target } => {
+                self.get_mut(target).unwrap().last_mut().unwrap().hide();
             }
         }
+    }
+}
+
+fn check_built_on(card: &Card, other: &Card) -> Result<(), MoveError> {
+    if card.can_be_built_on(other) {
+        Ok(())
+    } else if card.rank.0 + 1 != other.rank.0 {
+        Err(MoveError::RankMismatch)
+    } else {
+        Err(MoveError::ColorMismatch)
+    }
+}
+
+fn check_founded_on(card: &Card, top_card: Option<&Card>) -> Result<(), MoveError> {
+    match top_card {
+        // Empty foundations can only be transferred an ace.
+        None if card.rank == Rank::ACE => Ok(()),
+        None => Err(MoveError::RankMismatch),
+        // For established foundations, the suit must match and
+        // cards must be stacked with ranks ascending.
+        Some(top_card) if top_card.suit != card.suit => Err(MoveError::SuitMismatch),
+        Some(top_card) if card.rank.0 != top_card.rank.0 + 1 => Err(MoveError::RankMismatch),
+        Some(_) => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::DrawMode;
 
-        if was_transferred {
-            // Need to do this outside the for-loop to please the borrow checker.
-            self.get_mut(target).unwrap().pop().unwrap();
+    /**
+     * Set up a board with the given tableau piles, leaving every other card face down in the stock.
+     */
+    fn with_tableau(piles: &[&str]) -> Board {
+        let mut tableau = vec![Pile::new(); 7];
+
+        for (pile, cards) in tableau.iter_mut().zip(piles) {
+            *pile = cards
+                .split_whitespace()
+                .map(|card| card.parse().unwrap())
+                .collect();
         }
 
-        was_transferred
+        let stock = get_standard_pack()
+            .into_iter()
+            .filter(|card| {
+                !tableau
+                    .iter()
+                    .flatten()
+                    .any(|other| other.rank == card.rank && other.suit == card.suit)
+            })
+            .collect();
+
+        let foundations = vec![Pile::new(); 4];
+
+        Board::from_piles(
+            None,
+            Rules::default(),
+            tableau,
+            foundations,
+            stock,
+            Pile::new(),
+        )
+        .unwrap()
     }
 
-    pub fn maybe_move_top_stock_card_to_waste(&mut self) -> bool {
-        if let Some(&top_card) = self.stock.last().filter(|c| c.is_visible()) {
-            self.stock.pop();
-            self.waste.push(top_card);
-            true
-        } else {
-            false
+    /**
+     * Set up a board with the given cards face down in the stock, last card on top,
+     * leaving every other card face down in the first tableau pile.
+     */
+    fn with_stock(cards: &str, rules: Rules) -> Board {
+        let stock = parse_cards(cards);
+
+        let mut tableau = vec![Pile::new(); 7];
+        tableau[0] = get_standard_pack()
+            .into_iter()
+            .filter(|card| {
+                !stock
+                    .iter()
+                    .any(|other| other.rank == card.rank && other.suit == card.suit)
+            })
+            .collect();
+
+        let foundations = vec![Pile::new(); 4];
+
+        Board::from_piles(None, rules, tableau, foundations, stock, Pile::new()).unwrap()
+    }
+
+    fn parse_cards(cards: &str) -> Pile {
+        cards
+            .split_whitespace()
+            .map(|card| card.parse().unwrap())
+            .collect()
+    }
+
+    /**
+     * Play a move, then check that undoing it restores the position and that redoing
+     * it plays it again.
+     */
+    fn check_undo_and_redo(board: &mut Board, mv: Move) {
+        let before = (board.to_string(), board.get_pass());
+        board.apply(mv).unwrap();
+        let after = (board.to_string(), board.get_pass());
+
+        board.undo().unwrap();
+        assert_eq!((board.to_string(), board.get_pass()), before);
+
+        board.redo().unwrap();
+        assert_eq!((board.to_string(), board.get_pass()), after);
+    }
+
+    #[test]
+    fn reads_back_written_targets() {
+        let targets = [Target::Stock, Target::Waste]
+            .into_iter()
+            .chain((0..7).map(Target::Pile))
+            .chain((0..4).map(Target::Foundation));
+
+        for target in targets {
+            assert_eq!(target.to_string().parse(), Ok(target));
+        }
+
+        for value in ["", "0", "03", "+3", "P3", "F", "F0", "FF1", "s"] {
+            assert_eq!(
+                value.parse::<Target>(),
+                Err(InvalidTarget(value.to_string()))
+            );
         }
     }
 
-    pub fn reload_stock(&mut self) -> bool {
-        while let Some(mut card) = self.waste.pop() {
-            card.hide();
-            self.stock.push(card);
+    #[test]
+    fn deals_the_same_cards_for_a_deal_number() {
+        assert_eq!(
+            Board::from_seed(1).to_string(),
+            concat!(
+                "stock: ?AS ?10D ?KD ?5C ?8H ?2S ?5H ?10H ?AC ?QH ?7S ?3D ?JH ?7D ?5S ?6H ?JS ?QC ?8S ?AH ?4C ?QS ?7C ?9C\n",
+                "waste: -\n",
+                "foundations: - | - | - | -\n",
+                "tableau: 4S | ?KS JD | ?10C ?KH 8D | ?9D ?9S ?3C 8C | ?JC ?4D ?7H ?KC 2D | ?10S ?QD ?6C ?3H ?4H 2H | ?2C ?6D ?3S ?6S ?9H ?AD 5D\n",
+            )
+        );
+    }
+
+    #[test]
+    fn builds_in_alternating_colors() {
+        let board = with_tableau(&["8D", "9S"]);
+
+        assert_eq!(
+            board.check_transfer(Target::Pile(0), Target::Pile(1), 1),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_builds_in_the_same_color() {
+        let board = with_tableau(&["8S", "9C", "8C", "9S"]);
+
+        assert_eq!(
+            board.check_transfer(Target::Pile(0), Target::Pile(1), 1),
+            Err(MoveError::ColorMismatch)
+        );
+        assert_eq!(
+            board.check_transfer(Target::Pile(2), Target::Pile(3), 1),
+            Err(MoveError::ColorMismatch)
+        );
+    }
+
+    #[test]
+    fn moves_runs_in_alternating_colors() {
+        let board = with_tableau(&["?2H 8D 7S 6H", "9C"]);
+
+        assert_eq!(
+            board.check_transfer(Target::Pile(0), Target::Pile(1), 3),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_runs_breaking_the_color_alternation() {
+        let board = with_tableau(&["?2H 8D 7S 6C", "9C"]);
+
+        assert_eq!(
+            board.check_transfer(Target::Pile(0), Target::Pile(1), 3),
+            Err(MoveError::ColorMismatch)
+        );
+        // The top card alone is only rejected for its rank.
+        assert_eq!(
+            board.check_transfer(Target::Pile(0), Target::Pile(1), 1),
+            Err(MoveError::RankMismatch)
+        );
+    }
+
+    #[test]
+    fn only_moves_kings_to_empty_piles() {
+        let board = with_tableau(&["KH", "QS", "?3D JH 10S", ""]);
+
+        assert_eq!(
+            board.check_transfer(Target::Pile(0), Target::Pile(3), 1),
+            Ok(())
+        );
+        assert_eq!(
+            board.check_transfer(Target::Pile(1), Target::Pile(3), 1),
+            Err(MoveError::RankMismatch)
+        );
+        assert_eq!(
+            board.check_transfer(Target::Pile(2), Target::Pile(3), 2),
+            Err(MoveError::RankMismatch)
+        );
+    }
+
+    #[test]
+    fn deals_three_cards_at_a_time() {
+        let mut board = with_stock(
+            "?2C ?3C ?4C ?5C ?6C ?7C",
+            Rules::default().draw_mode(DrawMode::Three),
+        );
+
+        board.deal_from_stock().unwrap();
+
+        // The top card of the stock ends up at the bottom of the cards dealt.
+        assert_eq!(board.get_waste(), &parse_cards("7C 6C 5C"));
+        assert_eq!(board.get_stock(), &parse_cards("?2C ?3C ?4C"));
+    }
+
+    #[test]
+    fn deals_the_last_cards_left_in_the_stock() {
+        let mut board = with_stock(
+            "?2C ?3C ?4C ?5C ?6C",
+            Rules::default().draw_mode(DrawMode::Three),
+        );
+
+        board.deal_from_stock().unwrap();
+        board.deal_from_stock().unwrap();
+
+        assert_eq!(board.get_waste(), &parse_cards("6C 5C 4C 3C 2C"));
+        assert!(board.get_stock().is_empty());
+        assert_eq!(board.deal_from_stock(), Err(MoveError::EmptySource));
+    }
+
+    #[test]
+    fn reloads_the_stock_in_dealing_order() {
+        let mut board = with_stock("?2C ?3C ?4C", Rules::default());
+
+        while board.deal_from_stock().is_ok() {}
+        board.reload_stock().unwrap();
+
+        assert_eq!(board.get_stock(), &parse_cards("?2C ?3C ?4C"));
+        assert!(board.get_waste().is_empty());
+        assert_eq!(board.get_pass(), 2);
+
+        board.deal_from_stock().unwrap();
+
+        assert_eq!(board.get_waste(), &parse_cards("4C"));
+    }
+
+    #[test]
+    fn limits_the_passes_through_the_stock() {
+        let mut board = with_stock("?2C ?3C", Rules::default().passes(Passes::Limited(2)));
+
+        assert_eq!(board.get_remaining_passes(), Some(1));
+
+        while board.deal_from_stock().is_ok() {}
+        board.reload_stock().unwrap();
+
+        assert_eq!(board.get_remaining_passes(), Some(0));
+
+        while board.deal_from_stock().is_ok() {}
+
+        assert_eq!(board.reload_stock(), Err(MoveError::NoPassesLeft));
+        assert_eq!(board.get_pass(), 2);
+    }
+
+    #[test]
+    fn reloads_the_stock_as_often_as_needed_by_default() {
+        let mut board = with_stock("?2C", Rules::default());
+
+        for _ in 0..10 {
+            board.deal_from_stock().unwrap();
+            board.reload_stock().unwrap();
+        }
+
+        assert_eq!(board.get_remaining_passes(), None);
+        assert_eq!(board.get_pass(), 11);
+    }
+
+    #[test]
+    fn undoes_and_redoes_transfers() {
+        let mut board = with_tableau(&["?2H 8D 7S", "9S"]);
+
+        check_undo_and_redo(
+            &mut board,
+            Move::Transfer {
+                source: Target::Pile(0),
+                dest: Target::Pile(1),
+                num_cards: 2,
+            },
+        );
+    }
+
+    #[test]
+    fn undoes_and_redoes_deals() {
+        let mut board = with_stock(
+            "?2C ?3C ?4C ?5C",
+            Rules::default().draw_mode(DrawMode::Three),
+        );
+
+        check_undo_and_redo(&mut board, Move::Deal);
+        check_undo_and_redo(&mut board, Move::Deal);
+    }
+
+    #[test]
+    fn undoes_and_redoes_reloads() {
+        let mut board = with_stock("?2C ?3C", Rules::default());
+        board.deal_from_stock().unwrap();
+        board.deal_from_stock().unwrap();
+
+        check_undo_and_redo(&mut board, Move::Reload);
+        assert_eq!(board.get_pass(), 2);
+    }
+
+    #[test]
+    fn undoes_and_redoes_reveals() {
+        let mut board = with_tableau(&["?2H"]);
+
+        check_undo_and_redo(
+            &mut board,
+            Move::Reveal {
+                target: Target::Pile(0),
+            },
+        );
+    }
+
+    #[test]
+    fn undoes_and_redoes_moves_made_of_several_commands() {
+        let mut board = with_tableau(&["?2H 8D", "9S"]);
+        board.rules = board.rules.reveal(Reveal::Auto);
+
+        check_undo_and_redo(
+            &mut board,
+            Move::Transfer {
+                source: Target::Pile(0),
+                dest: Target::Pile(1),
+                num_cards: 1,
+            },
+        );
+        // Turning over the 2 of hearts was part of the move.
+        assert_eq!(board.get_history().len(), 1);
+        assert!(board.get(Target::Pile(0)).unwrap()[0].is_visible());
+    }
+
+    #[test]
+    fn reveals_the_card_left_face_down_along_with_the_move() {
+        let mut board = with_tableau(&["?2H ?5C 8D", "9S"]);
+        board.rules = board.rules.reveal(Reveal::Auto);
+
+        board
+            .apply(Move::Transfer {
+                source: Target::Pile(0),
+                dest: Target::Pile(1),
+                num_cards: 1,
+            })
+            .unwrap();
+
+        // Only the card left on top is turned over, as part of the same move.
+        assert_eq!(board.get_history().len(), 1);
+        assert_eq!(board.get(Target::Pile(0)), Some(&parse_cards("?2H 5C")));
+
+        board.undo().unwrap();
+
+        assert!(board.get_history().is_empty());
+        assert_eq!(board.get(Target::Pile(0)), Some(&parse_cards("?2H ?5C 8D")));
+        assert_eq!(board.get(Target::Pile(1)), Some(&parse_cards("9S")));
+    }
+
+    #[test]
+    fn forgets_undone_moves_when_playing_a_new_one() {
+        let mut board = with_stock("?2C ?3C", Rules::default());
+        board.deal_from_stock().unwrap();
+        board.undo().unwrap();
+
+        board.reveal(Target::Pile(0)).unwrap();
+
+        assert_eq!(board.redo(), Err(MoveError::NothingToRedo));
+    }
+
+    #[test]
+    fn lists_every_legal_move() {
+        let board = "
+            stock: -
+            waste: KC
+            foundations: AS 2S | AH | - | -
+            tableau: - | - | ?QD 9C 8H 7C | 10D 9S | 3H | 2H | ?3S ?4S ?5S ?6S ?7S ?8S ?10S ?JS ?QS ?KS ?4H ?5H ?6H ?7H ?9H ?10H ?JH ?QH ?AD ?2D ?3D ?4D ?5D ?6D ?7D ?8D ?9D ?JD ?KD ?AC ?2C ?3C ?4C ?5C ?6C ?8C ?10C ?JC ?QC KH
+        "
+        .parse::<Board>()
+        .unwrap();
+
+        let transfer = |source, dest, num_cards| Move::Transfer {
+            source,
+            dest,
+            num_cards,
+        };
+
+        assert_eq!(
+            board.legal_moves().collect::<Vec<_>>(),
+            vec![
+                Move::Reload,
+                transfer(Target::Waste, Target::Pile(0), 1),
+                transfer(Target::Waste, Target::Pile(1), 1),
+                transfer(Target::Pile(2), Target::Pile(3), 2),
+                transfer(Target::Pile(5), Target::Foundation(1), 1),
+                transfer(Target::Pile(6), Target::Pile(0), 1),
+                transfer(Target::Pile(6), Target::Pile(1), 1),
+                transfer(Target::Foundation(0), Target::Pile(4), 1),
+            ]
+        );
+
+        // Kings go to either empty pile, which only counts once.
+        assert_eq!(
+            board.distinct_moves().collect::<Vec<_>>(),
+            vec![
+                Move::Reload,
+                transfer(Target::Waste, Target::Pile(0), 1),
+                transfer(Target::Pile(2), Target::Pile(3), 2),
+                transfer(Target::Pile(5), Target::Foundation(1), 1),
+                transfer(Target::Pile(6), Target::Pile(0), 1),
+                transfer(Target::Foundation(0), Target::Pile(4), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn plays_every_listed_move() {
+        let variants = [
+            Rules::default(),
+            Rules::default().draw_mode(DrawMode::Three),
+            Rules::default().passes(Passes::Limited(1)),
+            Rules::default().reveal(Reveal::Auto),
+        ];
+
+        for (seed, rules) in (0..20).zip(variants.into_iter().cycle()) {
+            let mut board = Board::deal(seed, rules);
+            let mut rng = DealRng::new(seed);
+
+            for _ in 0..200 {
+                let moves = board.legal_moves().collect::<Vec<_>>();
+
+                for &mv in &moves {
+                    assert_eq!(board.clone().apply(mv), Ok(()), "{mv:?} in\n{board}");
+                }
+
+                if moves.is_empty() {
+                    break;
+                }
+
+                board.apply(moves[rng.next_below(moves.len())]).unwrap();
+            }
         }
-        true
+    }
+
+    /**
+     * Play the 8 of clubs onto the 9 of diamonds, which must be on the last two piles,
+     * so that cards can be autoplayed along with it.
+     */
+    fn play_a_move(board: &mut Board) {
+        board
+            .apply(Move::Transfer {
+                source: Target::Pile(6),
+                dest: Target::Pile(5),
+                num_cards: 1,
+            })
+            .unwrap();
+    }
+
+    fn get_num_founded(board: &Board) -> usize {
+        board.get_foundations().iter().map(Vec::len).sum()
+    }
+
+    #[test]
+    fn autoplays_aces_and_twos() {
+        let mut board = with_tableau(&["AS", "?KD 2S", "2H", "", "", "9D", "8C"]);
+        play_a_move(&mut board);
+
+        assert_eq!(board.autoplay(), 2);
+        assert_eq!(board.get_foundations()[0], parse_cards("AS 2S"));
+        // The 2 of hearts has no ace to go on yet.
+        assert_eq!(board.get(Target::Pile(2)), Some(&parse_cards("2H")));
+    }
+
+    #[test]
+    fn holds_back_cards_which_may_be_built_on() {
+        let mut board = with_tableau(&["3S", "2S", "AS", "2H AH", "AD", "9D", "8C"]);
+        play_a_move(&mut board);
+
+        // The 2 of diamonds could still go on the 3 of spades.
+        assert_eq!(board.autoplay(), 5);
+        assert_eq!(board.get(Target::Pile(0)), Some(&parse_cards("3S")));
+    }
+
+    #[test]
+    fn autoplays_cards_once_both_lower_cards_of_the_other_color_are_founded() {
+        let mut board = with_tableau(&["3S", "2S", "AS", "2H AH", "2D AD", "9D", "8C"]);
+        play_a_move(&mut board);
+
+        assert_eq!(board.autoplay(), 7);
+        assert!(board.get(Target::Pile(0)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn undoes_autoplayed_cards_along_with_the_move() {
+        let mut board = with_tableau(&["3S", "2S", "AS", "2H AH", "2D AD", "9D", "8C"]);
+        let before = board.to_string();
+
+        play_a_move(&mut board);
+        board.autoplay();
+        let after = board.to_string();
+
+        board.undo().unwrap();
+
+        assert_eq!(board.to_string(), before);
+        assert_eq!(get_num_founded(&board), 0);
+        assert!(board.get_history().is_empty());
+
+        board.redo().unwrap();
+
+        assert_eq!(board.to_string(), after);
+        assert_eq!(get_num_founded(&board), 7);
+    }
+
+    #[test]
+    fn only_autoplays_along_with_a_move() {
+        let mut board = with_tableau(&["AS"]);
+
+        assert_eq!(board.autoplay(), 0);
+        assert_eq!(get_num_founded(&board), 0);
     }
 }
@@ -1,4 +1,14 @@
+use std::{fmt, str::FromStr};
+
+use super::PositionError;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Black,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Suit {
     Spades,
     Heart,
@@ -10,22 +20,32 @@ impl Suit {
     pub fn all() -> [Self; 4] {
         [Self::Heart, Self::Diamond, Self::Spades, Self::Club]
     }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Self::Heart | Self::Diamond => Color::Red,
+            Self::Spades | Self::Club => Color::Black,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Rank(pub u8);
 
 impl Rank {
+    pub const ACE: Self = Self(1);
+    pub const KING: Self = Self(13);
+
     pub fn new(value: u8) -> Self {
         Self(value)
     }
 
     pub fn all() -> Vec<Self> {
-        (1..=13).map(|n| Self::new(n)).collect()
+        (1..=13).map(Self::new).collect()
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Card {
     pub rank: Rank,
     pub suit: Suit,
@@ -52,6 +72,101 @@ impl Card {
     pub fn hide(&mut self) {
         self.shown = false;
     }
+
+    /**
+     * Whether this card can be placed onto `other` in the tableau,
+     * i.e. it has the opposite color and a rank just below.
+     */
+    pub fn can_be_built_on(&self, other: &Card) -> bool {
+        self.suit.color() != other.suit.color() && self.rank.0 + 1 == other.rank.0
+    }
+}
+
+/**
+ * Suits are written as their initial: `S`, `H`, `C` or `D`.
+ */
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Suit::Spades => "S",
+            Suit::Heart => "H",
+            Suit::Club => "C",
+            Suit::Diamond => "D",
+        };
+
+        write!(f, "{symbol}")
+    }
+}
+
+/**
+ * Ranks are written `A`, `2` to `10`, `J`, `Q` and `K`.
+ */
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            1 => write!(f, "A"),
+            11 => write!(f, "J"),
+            12 => write!(f, "Q"),
+            13 => write!(f, "K"),
+            n => write!(f, "{n}"),
+        }
+    }
+}
+
+/**
+ * Cards are written as their rank followed by their suit, e.g. `QH` or `10S`,
+ * with a `?` prefix when face down.
+ */
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = if self.shown { "" } else { "?" };
+        write!(f, "{prefix}{}{}", self.rank, self.suit)
+    }
+}
+
+impl FromStr for Card {
+    type Err = PositionError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || PositionError::InvalidCard(value.to_string());
+
+        let (shown, rest) = match value.strip_prefix('?') {
+            Some(rest) => (false, rest),
+            None => (true, value),
+        };
+
+        let suit = match rest.chars().last() {
+            Some('S') => Suit::Spades,
+            Some('H') => Suit::Heart,
+            Some('C') => Suit::Club,
+            Some('D') => Suit::Diamond,
+            _ => return Err(invalid()),
+        };
+
+        // Ranks are matched literally, so that e.g. `05H` or `+5H` aren't taken for cards.
+        let rank = match &rest[..rest.len() - 1] {
+            "A" => 1,
+            "2" => 2,
+            "3" => 3,
+            "4" => 4,
+            "5" => 5,
+            "6" => 6,
+            "7" => 7,
+            "8" => 8,
+            "9" => 9,
+            "10" => 10,
+            "J" => 11,
+            "Q" => 12,
+            "K" => 13,
+            _ => return Err(invalid()),
+        };
+
+        Ok(Self {
+            rank: Rank::new(rank),
+            suit,
+            shown,
+        })
+    }
 }
 
 pub fn get_standard_pack() -> Vec<Card> {
@@ -65,3 +180,30 @@ pub fn get_standard_pack() -> Vec<Card> {
 
     pack
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_back_written_cards() {
+        for mut card in get_standard_pack() {
+            assert_eq!(card.to_string().parse(), Ok(card));
+
+            card.reveal();
+            assert_eq!(card.to_string().parse(), Ok(card));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_cards() {
+        for value in [
+            "", "?", "H", "1H", "11H", "05H", "+5H", " 5H", "5h", "5X", "??5H",
+        ] {
+            assert_eq!(
+                value.parse::<Card>(),
+                Err(PositionError::InvalidCard(value.to_string()))
+            );
+        }
+    }
+}
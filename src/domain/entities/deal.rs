@@ -0,0 +1,64 @@
+/**
+ * Pseudo-random number generator used to shuffle deals.
+ *
+ * Deals must stay the same across versions and platforms for deal numbers to be
+ * shareable, so we use our own implementation of SplitMix64 rather than one from
+ * a library which may change its algorithm.
+ */
+pub struct DealRng {
+    state: u64,
+}
+
+impl DealRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /**
+     * Return a number in `0..n`.
+     */
+    pub fn next_below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /**
+     * Shuffle items in place using the Fisher-Yates algorithm.
+     */
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.next_below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_splitmix64_reference() {
+        let mut rng = DealRng::new(0);
+
+        assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+        assert_eq!(rng.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+        assert_eq!(rng.next_u64(), 0x06c4_5d18_8009_454f);
+    }
+
+    #[test]
+    fn generates_the_same_numbers_for_a_seed() {
+        let mut rng = DealRng::new(1);
+
+        assert_eq!(rng.next_u64(), 0x910a_2dec_8902_5cc1);
+        assert_eq!(rng.next_u64(), 0xbeeb_8da1_658e_ec67);
+        assert_eq!(rng.next_u64(), 0xf893_a2ee_fb32_555e);
+    }
+}
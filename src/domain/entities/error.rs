@@ -0,0 +1,122 @@
+use std::{error::Error, fmt};
+
+use super::Card;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    NoSuchPile,
+    EmptySource,
+    NotEnoughCards,
+    HiddenCard,
+    RankMismatch,
+    ColorMismatch,
+    SuitMismatch,
+    IllegalDestination,
+    StockNotEmpty,
+    NoPassesLeft,
+    GameOver,
+    AlreadyRevealed,
+    NothingToUndo,
+    NothingToRedo,
+    NoMoveAvailable,
+    CantAutoComplete,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            MoveError::NoSuchPile => "There is no such pile",
+            MoveError::EmptySource => "There is no card to move",
+            MoveError::NotEnoughCards => "There are not enough cards to move",
+            MoveError::HiddenCard => "Face down cards can't be moved",
+            MoveError::RankMismatch => "Ranks must follow each other",
+            MoveError::ColorMismatch => "Colors must alternate",
+            MoveError::SuitMismatch => "Suits must match",
+            MoveError::IllegalDestination => "Cards can't be moved there",
+            MoveError::StockNotEmpty => "The stock can only be reloaded once empty",
+            MoveError::NoPassesLeft => "The stock can't be reloaded anymore",
+            MoveError::GameOver => "The game is over",
+            MoveError::AlreadyRevealed => "This card is already face up",
+            MoveError::NothingToUndo => "There is no move to undo",
+            MoveError::NothingToRedo => "There is no move to redo",
+            MoveError::NoMoveAvailable => "There is no move worth playing",
+            MoveError::CantAutoComplete => {
+                "Cards can only be played automatically once all are face up"
+            }
+        };
+
+        write!(f, "{reason}")
+    }
+}
+
+impl Error for MoveError {}
+
+/**
+ * Reasons why a position can't be set up, e.g. when resuming a saved game.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PositionError {
+    InvalidCard(String),
+    InvalidSection(String),
+    MissingSection(&'static str),
+    WrongNumberOfPiles,
+    MissingCard(Card),
+    DuplicateCard(Card),
+    /**
+     * The card breaks the run of its foundation, which must go up from the ace
+     * in a single suit, face up.
+     */
+    MisplacedOnFoundation(Card),
+    InvalidPass,
+    InvalidHistory,
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::InvalidCard(card) => write!(f, "'{card}' is not a card"),
+            PositionError::InvalidSection(line) => write!(f, "Unexpected line '{line}'"),
+            PositionError::MissingSection(name) => write!(f, "The {name} is missing"),
+            PositionError::WrongNumberOfPiles => {
+                write!(f, "There must be 7 tableau piles and 4 foundations")
+            }
+            PositionError::MissingCard(card) => write!(f, "{card} is missing"),
+            PositionError::DuplicateCard(card) => write!(f, "{card} appears more than once"),
+            PositionError::MisplacedOnFoundation(card) => {
+                write!(f, "{card} can't be on its foundation")
+            }
+            PositionError::InvalidPass => write!(f, "The pass through the stock is out of range"),
+            PositionError::InvalidHistory => write!(f, "Moves don't match the position"),
+        }
+    }
+}
+
+impl Error for PositionError {}
+
+/**
+ * A rule option which isn't understood, such as an unknown variant.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidRule(pub String);
+
+impl fmt::Display for InvalidRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid rule '{}'", self.0)
+    }
+}
+
+impl Error for InvalidRule {}
+
+/**
+ * A pile which isn't written in target notation, e.g. `P3` rather than `3`.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidTarget(pub String);
+
+impl fmt::Display for InvalidTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is not a pile", self.0)
+    }
+}
+
+impl Error for InvalidTarget {}
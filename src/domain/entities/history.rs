@@ -0,0 +1,100 @@
+use super::Target;
+
+/**
+ * A reversible change to the board.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Transfer {
+        source: Target,
+        dest: Target,
+        num_cards: usize,
+    },
+    Deal {
+        num_cards: usize,
+    },
+    Reload,
+    Reveal {
+        target: Target,
+    },
+}
+
+/**
+ * Commands played so far, grouped by move, along with undone moves that can be redone.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    done: Vec<Vec<Command>>,
+    undone: Vec<Vec<Command>>,
+}
+
+impl History {
+    /**
+     * Restore a history, e.g. from a saved game. Undone moves are listed
+     * in the order they were undone, so the last one is redone first.
+     */
+    pub fn from_moves(done: Vec<Vec<Command>>, undone: Vec<Vec<Command>>) -> Self {
+        Self { done, undone }
+    }
+
+    pub fn record(&mut self, commands: Vec<Command>) {
+        self.done.push(commands);
+        // Playing a new move forgets about any undone moves.
+        self.undone.clear();
+    }
+
+    /**
+     * Add a command to the last move, e.g. one that was played automatically along with it.
+     */
+    pub fn append(&mut self, command: Command) {
+        match self.done.last_mut() {
+            Some(commands) => commands.push(command),
+            None => self.done.push(vec![command]),
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /**
+     * Number of moves played so far, not counting undone moves.
+     */
+    pub fn len(&self) -> usize {
+        self.done.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.done.is_empty()
+    }
+
+    pub fn undo(&mut self) -> Option<Vec<Command>> {
+        let commands = self.done.pop()?;
+        self.undone.push(commands.clone());
+        Some(commands)
+    }
+
+    pub fn redo(&mut self) -> Option<Vec<Command>> {
+        let commands = self.undone.pop()?;
+        self.done.push(commands.clone());
+        Some(commands)
+    }
+
+    /**
+     * Iterate over moves played so far, oldest first.
+     */
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Vec<Command>> {
+        self.done.iter()
+    }
+
+    /**
+     * Iterate over undone moves, in the order they were undone.
+     */
+    pub fn iter_undone(&self) -> impl DoubleEndedIterator<Item = &Vec<Command>> {
+        self.undone.iter()
+    }
+}
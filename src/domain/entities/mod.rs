@@ -1,5 +1,19 @@
 mod board;
 mod card;
+mod deal;
+mod error;
+mod history;
+mod moves;
+mod outcome;
+mod position;
+mod rules;
 
 pub use board::*;
 pub use card::*;
+pub use deal::*;
+pub use error::*;
+pub use history::*;
+pub use moves::*;
+pub use outcome::*;
+pub use position::*;
+pub use rules::*;
@@ -0,0 +1,102 @@
+use std::fmt;
+
+use super::{Command, Target};
+
+/**
+ * A move a player can make on the board.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Move {
+    Transfer {
+        source: Target,
+        dest: Target,
+        num_cards: usize,
+    },
+    Deal,
+    Reload,
+    Reveal {
+        target: Target,
+    },
+}
+
+impl Move {
+    /**
+     * Pile the cards are taken from.
+     */
+    pub fn get_source(&self) -> Target {
+        match *self {
+            Move::Transfer { source, .. } => source,
+            Move::Deal => Target::Stock,
+            Move::Reload => Target::Waste,
+            Move::Reveal { target } => target,
+        }
+    }
+
+    /**
+     * Pile the cards end up on.
+     */
+    pub fn get_dest(&self) -> Target {
+        match *self {
+            Move::Transfer { dest, .. } => dest,
+            Move::Deal => Target::Waste,
+            Move::Reload => Target::Stock,
+            Move::Reveal { target } => target,
+        }
+    }
+
+    /**
+     * Number of cards moved, as shown at the source.
+     */
+    pub fn get_num_cards(&self) -> usize {
+        match *self {
+            Move::Transfer { num_cards, .. } => num_cards,
+            _ => 1,
+        }
+    }
+}
+
+impl From<Command> for Move {
+    fn from(command: Command) -> Self {
+        match command {
+            Command::Transfer {
+                source,
+                dest,
+                num_cards,
+            } => Move::Transfer {
+                source,
+                dest,
+                num_cards,
+            },
+            Command::Deal { .. } => Move::Deal,
+            Command::Reload => Move::Reload,
+            Command::Reveal { target } => Move::Reveal { target },
+        }
+    }
+}
+
+/**
+ * Moves are written the way they are played:
+ *
+ * - `3>5x2` moves two cards from the third tableau pile to the fifth one,
+ *   and the count is left out for a single card, e.g. `W>F1`;
+ * - `S` turns the stock over, either dealing from it or reloading it;
+ * - `^4` reveals the top card of the fourth tableau pile.
+ */
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Move::Transfer {
+                source,
+                dest,
+                num_cards: 1,
+            } => write!(f, "{source}>{dest}"),
+            Move::Transfer {
+                source,
+                dest,
+                num_cards,
+            } => write!(f, "{source}>{dest}x{num_cards}"),
+            Move::Deal | Move::Reload => write!(f, "S"),
+            Move::Reveal { target } => write!(f, "^{target}"),
+        }
+    }
+}
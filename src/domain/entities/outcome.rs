@@ -0,0 +1,9 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameOutcome {
+    InProgress,
+    Won,
+    /**
+     * No move can be played anymore.
+     */
+    Stuck,
+}
@@ -0,0 +1,200 @@
+use std::{fmt, str::FromStr};
+
+use super::{Board, Pile, PositionError, Rules};
+
+/**
+ * Positions are written as four lines, listing piles from bottom to top card:
+ *
+ * ```text
+ * stock: ?4H ?KS ?2C
+ * waste: 5D 6C
+ * foundations: AH 2H | AS | - | -
+ * tableau: 4S | ?QH 9S | ?6C ?8D 7C | - | ...
+ * ```
+ *
+ * Face down cards have a `?` prefix, and empty piles are written `-`.
+ */
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "stock: {}", format_pile(self.get_stock()))?;
+        writeln!(f, "waste: {}", format_pile(self.get_waste()))?;
+        writeln!(f, "foundations: {}", format_piles(self.get_foundations()))?;
+        writeln!(f, "tableau: {}", format_piles(self.get_tableau()))
+    }
+}
+
+/**
+ * Set up a position with default rules, checking that it holds each card of the pack exactly once.
+ */
+impl FromStr for Board {
+    type Err = PositionError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Board::from_position(text, Rules::default())
+    }
+}
+
+impl Board {
+    /**
+     * Set up a position written in position notation, to be played with the given rules.
+     */
+    pub fn from_position(text: &str, rules: Rules) -> Result<Self, PositionError> {
+        let mut stock = None;
+        let mut waste = None;
+        let mut foundations = None;
+        let mut tableau = None;
+
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let invalid = || PositionError::InvalidSection(line.to_string());
+
+            let (name, value) = line.split_once(':').ok_or_else(invalid)?;
+
+            let section = match name.trim() {
+                "stock" => &mut stock,
+                "waste" => &mut waste,
+                "foundations" => &mut foundations,
+                "tableau" => &mut tableau,
+                _ => return Err(invalid()),
+            };
+
+            if section.is_some() {
+                return Err(invalid());
+            }
+
+            *section = Some(value.trim());
+        }
+
+        let stock = stock.ok_or(PositionError::MissingSection("stock"))?;
+        let waste = waste.ok_or(PositionError::MissingSection("waste"))?;
+        let foundations = foundations.ok_or(PositionError::MissingSection("foundations"))?;
+        let tableau = tableau.ok_or(PositionError::MissingSection("tableau"))?;
+
+        Board::from_piles(
+            None,
+            rules,
+            parse_piles(tableau)?,
+            parse_piles(foundations)?,
+            parse_pile(stock)?,
+            parse_pile(waste)?,
+        )
+    }
+}
+
+/**
+ * Write the cards of a pile from bottom to top, or `-` if it is empty.
+ */
+pub fn format_pile(pile: &Pile) -> String {
+    if pile.is_empty() {
+        return "-".to_string();
+    }
+
+    pile.iter()
+        .map(|card| card.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn format_piles(piles: &[Pile]) -> String {
+    piles
+        .iter()
+        .map(format_pile)
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/**
+ * Read back a pile written by `format_pile()`.
+ */
+pub fn parse_pile(value: &str) -> Result<Pile, PositionError> {
+    match value.trim() {
+        "" | "-" => Ok(Pile::new()),
+        value => value.split_whitespace().map(str::parse).collect(),
+    }
+}
+
+fn parse_piles(value: &str) -> Result<Vec<Pile>, PositionError> {
+    value.split('|').map(parse_pile).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{get_standard_pack, Card};
+
+    #[test]
+    fn reads_back_written_positions() {
+        let mut board = Board::from_seed(7);
+        board.deal_from_stock().unwrap();
+
+        let position = board.to_string();
+
+        assert_eq!(position.parse::<Board>().unwrap().to_string(), position);
+    }
+
+    #[test]
+    fn rejects_duplicate_cards() {
+        // The ace of spades is at the bottom of the stock of deal 1, before the ten of diamonds.
+        let position = Board::from_seed(1).to_string().replace("?AS", "?10D");
+
+        assert_eq!(
+            position.parse::<Board>().unwrap_err(),
+            PositionError::DuplicateCard("10D".parse::<Card>().unwrap())
+        );
+    }
+
+    #[test]
+    fn rejects_foundations_not_built_up_from_the_ace() {
+        let with_foundation = |cards: &str| {
+            let foundation = cards
+                .split_whitespace()
+                .map(|card| card.parse().unwrap())
+                .collect::<Pile>();
+
+            let stock = get_standard_pack()
+                .into_iter()
+                .filter(|card| {
+                    !foundation
+                        .iter()
+                        .any(|other| other.rank == card.rank && other.suit == card.suit)
+                })
+                .collect();
+
+            let foundations = vec![foundation, Pile::new(), Pile::new(), Pile::new()];
+
+            Board::from_piles(
+                None,
+                Rules::default(),
+                vec![Pile::new(); 7],
+                foundations,
+                stock,
+                Pile::new(),
+            )
+        };
+
+        assert!(with_foundation("AS 2S 3S").is_ok());
+
+        for (cards, misplaced) in [
+            ("KS QS", "KS"),
+            ("2S", "2S"),
+            ("AS 2S 4S", "4S"),
+            ("AS 2H", "2H"),
+            ("?AS", "?AS"),
+            ("AS ?2S", "?2S"),
+        ] {
+            assert_eq!(
+                with_foundation(cards).unwrap_err(),
+                PositionError::MisplacedOnFoundation(misplaced.parse::<Card>().unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_missing_cards() {
+        let position = Board::from_seed(1).to_string().replace("?AS ", "");
+
+        assert_eq!(
+            position.parse::<Board>().unwrap_err(),
+            PositionError::MissingCard("AS".parse::<Card>().unwrap())
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::{fmt, str::FromStr};
+
+use super::InvalidRule;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DrawMode {
+    #[default]
+    One,
+    Three,
+}
+
+impl DrawMode {
+    pub fn num_cards(&self) -> usize {
+        match self {
+            DrawMode::One => 1,
+            DrawMode::Three => 3,
+        }
+    }
+}
+
+/**
+ * How many times the player may go through the stock.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Passes {
+    #[default]
+    Unlimited,
+    Limited(u32),
+}
+
+impl Passes {
+    /**
+     * Vegas rules allow three passes when drawing three cards, and a single one otherwise.
+     */
+    pub fn vegas(draw_mode: DrawMode) -> Self {
+        match draw_mode {
+            DrawMode::One => Passes::Limited(1),
+            DrawMode::Three => Passes::Limited(3),
+        }
+    }
+}
+
+/**
+ * How face down cards left on top of a pile get turned over.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Reveal {
+    /**
+     * The player turns them over, as a move of its own.
+     */
+    #[default]
+    Manual,
+    /**
+     * They are turned over as part of the move which exposed them.
+     */
+    Auto,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    #[default]
+    Klondike,
+    /**
+     * Klondike with a limited number of passes through the stock.
+     */
+    Vegas,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rules {
+    pub variant: Variant,
+    pub draw_mode: DrawMode,
+    pub passes: Passes,
+    pub reveal: Reveal,
+}
+
+impl Rules {
+    /**
+     * Default rules of a variant for the given draw mode.
+     */
+    pub fn for_variant(variant: Variant, draw_mode: DrawMode) -> Self {
+        let passes = match variant {
+            Variant::Klondike => Passes::Unlimited,
+            Variant::Vegas => Passes::vegas(draw_mode),
+        };
+
+        Self {
+            variant,
+            draw_mode,
+            passes,
+            reveal: Reveal::default(),
+        }
+    }
+
+    pub fn draw_mode(mut self, value: DrawMode) -> Self {
+        self.draw_mode = value;
+        self
+    }
+
+    pub fn passes(mut self, value: Passes) -> Self {
+        self.passes = value;
+        self
+    }
+
+    pub fn reveal(mut self, value: Reveal) -> Self {
+        self.reveal = value;
+        self
+    }
+}
+
+impl fmt::Display for DrawMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.num_cards())
+    }
+}
+
+impl FromStr for DrawMode {
+    type Err = InvalidRule;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "1" => Ok(DrawMode::One),
+            "3" => Ok(DrawMode::Three),
+            _ => Err(InvalidRule(value.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Passes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Passes::Unlimited => write!(f, "unlimited"),
+            Passes::Limited(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+impl FromStr for Passes {
+    type Err = InvalidRule;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "unlimited" => Ok(Passes::Unlimited),
+            _ => match value.parse() {
+                Ok(n) if n > 0 => Ok(Passes::Limited(n)),
+                _ => Err(InvalidRule(value.to_string())),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Reveal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reveal::Manual => write!(f, "manual"),
+            Reveal::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+impl FromStr for Reveal {
+    type Err = InvalidRule;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "manual" => Ok(Reveal::Manual),
+            "auto" => Ok(Reveal::Auto),
+            _ => Err(InvalidRule(value.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Variant::Klondike => write!(f, "klondike"),
+            Variant::Vegas => write!(f, "vegas"),
+        }
+    }
+}
+
+impl FromStr for Variant {
+    type Err = InvalidRule;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "klondike" => Ok(Variant::Klondike),
+            "vegas" => Ok(Variant::Vegas),
+            _ => Err(InvalidRule(value.to_string())),
+        }
+    }
+}
@@ -8,5 +8,68 @@ pub enum Action<'a> {
     DecreaseRange,
     Build(&'a mut Board),
     Act(&'a mut Board),
-    Discard(&'a mut Board),
+    Deal(&'a mut Board),
+    Undo(&'a mut Board),
+    Redo(&'a mut Board),
+    Hint(&'a Board),
+    /**
+     * Move one more card to the foundations once all cards are face up.
+     */
+    AutoComplete(&'a mut Board),
+}
+
+impl<'a> Action<'a> {
+    /**
+     * Board the action applies to, if any.
+     */
+    pub fn get_board(&self) -> Option<&Board> {
+        match self {
+            Action::TargetPrevious(board)
+            | Action::TargetNext(board)
+            | Action::IncreaseRange(board)
+            | Action::Hint(board) => Some(board),
+            Action::Build(board)
+            | Action::Act(board)
+            | Action::Deal(board)
+            | Action::Undo(board)
+            | Action::Redo(board)
+            | Action::AutoComplete(board) => Some(board),
+            Action::DecreaseRange => None,
+        }
+    }
+
+    /**
+     * Board the action applies to, if the action may change it.
+     */
+    pub fn get_board_mut(&mut self) -> Option<&mut Board> {
+        match self {
+            Action::Build(board)
+            | Action::Act(board)
+            | Action::Deal(board)
+            | Action::Undo(board)
+            | Action::Redo(board)
+            | Action::AutoComplete(board) => Some(board),
+            _ => None,
+        }
+    }
+
+    /**
+     * The same action, borrowing the board for a shorter while, so that the
+     * board can be looked at again once the action was handled.
+     */
+    pub fn reborrow(&mut self) -> Action<'_> {
+        match self {
+            Action::TargetPrevious(board) => Action::TargetPrevious(board),
+            Action::TargetNext(board) => Action::TargetNext(board),
+            Action::IncreaseRange(board) => Action::IncreaseRange(board),
+            Action::DecreaseRange => Action::DecreaseRange,
+            Action::Build(board) => Action::Build(board),
+            Action::Act(board) => Action::Act(board),
+            Action::Deal(board) => Action::Deal(board),
+            Action::Undo(board) => Action::Undo(board),
+            Action::Redo(board) => Action::Redo(board),
+            Action::Hint(board) => Action::Hint(board),
+            Action::AutoComplete(board) => Action::AutoComplete(board),
+        }
+    }
 }
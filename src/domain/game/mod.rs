@@ -2,8 +2,12 @@ mod actions;
 mod statemachine;
 
 mod state_base;
+mod state_hinting;
+mod state_replaying;
 mod state_selecting;
+mod state_stuck;
 mod state_transferring;
+mod state_won;
 
 pub use actions::Action;
 pub use state_base::TargetStatus;
@@ -1,13 +1,37 @@
-use crate::domain::Target;
+use crate::domain::{is_stuck, Board, GameOutcome, MoveError, Target};
 
-use super::Action;
+use super::{state_stuck::StuckState, state_won::WonState, Action};
 
 pub enum TargetStatus {
     Current { num_cards: usize },
     Picked { num_cards: usize },
+    Hinted { num_cards: usize },
 }
 
+/**
+ * Outcome of handling an action: whether anything changed, and the state to move to, if any.
+ */
+pub type HandleResult = Result<(bool, Option<Box<dyn GameState>>), MoveError>;
+
 pub trait GameState {
     fn get_status_of(&self, target: Target) -> Option<TargetStatus>;
-    fn handle(&mut self, action: Action) -> (bool, Option<Box<dyn GameState>>);
+    /**
+     * Target the player is pointing at, if any.
+     */
+    fn get_cursor(&self) -> Option<Target>;
+    fn get_outcome(&self) -> GameOutcome {
+        GameOutcome::InProgress
+    }
+    fn handle(&mut self, action: Action) -> HandleResult;
+}
+
+/**
+ * State to move to after a move was played, if the game is over or no move can make progress.
+ */
+pub fn get_end_state(board: &Board, cursor: Target) -> Option<Box<dyn GameState>> {
+    match board.get_outcome() {
+        GameOutcome::Won => Some(Box::new(WonState)),
+        _ if is_stuck(board) => Some(Box::new(StuckState::new(cursor))),
+        _ => None,
+    }
 }
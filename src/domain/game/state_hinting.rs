@@ -0,0 +1,73 @@
+use crate::domain::{get_hints, Board, Move, MoveError, Target};
+
+use super::{
+    state_base::{GameState, HandleResult},
+    state_selecting::SelectingState,
+    Action, TargetStatus,
+};
+
+/**
+ * Highlight a suggested move, cycling through alternatives when asked for another hint.
+ */
+pub struct HintingState {
+    current_target: Target,
+    hints: Vec<Move>,
+    index: usize,
+}
+
+impl HintingState {
+    pub fn new(current_target: Target, board: &Board) -> Result<Self, MoveError> {
+        let hints = get_hints(board);
+
+        if hints.is_empty() {
+            return Err(MoveError::NoMoveAvailable);
+        }
+
+        Ok(Self {
+            current_target,
+            hints,
+            index: 0,
+        })
+    }
+
+    pub fn get_hint(&self) -> Move {
+        self.hints[self.index]
+    }
+}
+
+impl GameState for HintingState {
+    fn get_status_of(&self, target: Target) -> Option<TargetStatus> {
+        let hint = self.get_hint();
+
+        if target == hint.get_source() {
+            return Some(TargetStatus::Hinted {
+                num_cards: hint.get_num_cards(),
+            });
+        }
+
+        if target == hint.get_dest() {
+            return Some(TargetStatus::Hinted { num_cards: 1 });
+        }
+
+        (target == self.current_target).then_some(TargetStatus::Current { num_cards: 1 })
+    }
+
+    fn get_cursor(&self) -> Option<Target> {
+        Some(self.current_target)
+    }
+
+    fn handle(&mut self, action: Action) -> HandleResult {
+        match action {
+            Action::Hint(_) => {
+                self.index = (self.index + 1) % self.hints.len();
+                Ok((true, None))
+            }
+            action => {
+                // Any other action dismisses the hint.
+                let mut state = SelectingState::new(self.current_target);
+                let (_, new_state) = state.handle(action)?;
+                Ok((true, Some(new_state.unwrap_or_else(|| Box::new(state)))))
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::domain::{Move, Target};
+
+use super::{
+    state_base::{GameState, HandleResult},
+    Action, TargetStatus,
+};
+
+/**
+ * Watch a recorded game, highlighting the last move played. Actions have no effect.
+ */
+pub struct ReplayingState {
+    last_move: Option<Move>,
+}
+
+impl ReplayingState {
+    pub fn new(last_move: Option<Move>) -> Self {
+        Self { last_move }
+    }
+}
+
+impl GameState for ReplayingState {
+    fn get_status_of(&self, target: Target) -> Option<TargetStatus> {
+        let last_move = self.last_move?;
+
+        if target == last_move.get_source() {
+            return Some(TargetStatus::Hinted {
+                num_cards: last_move.get_num_cards(),
+            });
+        }
+
+        (target == last_move.get_dest()).then_some(TargetStatus::Hinted { num_cards: 1 })
+    }
+
+    fn get_cursor(&self) -> Option<Target> {
+        None
+    }
+
+    fn handle(&mut self, _action: Action) -> HandleResult {
+        Ok((false, None))
+    }
+}
@@ -1,6 +1,11 @@
-use crate::domain::{Board, Target};
+use crate::domain::{Board, MoveError, Target};
 
-use super::{state_base::GameState, state_transferring::TransferringState, Action, TargetStatus};
+use super::{
+    state_base::{get_end_state, GameState, HandleResult},
+    state_hinting::HintingState,
+    state_transferring::TransferringState,
+    Action, TargetStatus,
+};
 
 pub struct SelectingState {
     current_target: Target,
@@ -21,23 +26,36 @@ impl SelectingState {
         }
     }
 
-    fn maybe_act(&mut self, board: &mut Board) -> (bool, Option<Box<dyn GameState>>) {
-        if let Some(top_card) = board.get_mut(self.current_target).unwrap().last_mut() {
+    fn deal_or_reload(&mut self, board: &mut Board) -> HandleResult {
+        if board.get_stock().is_empty() {
+            board.reload_stock()?;
+        } else {
+            board.deal_from_stock()?;
+        }
+
+        Ok((true, get_end_state(board, self.current_target)))
+    }
+
+    fn maybe_act(&mut self, board: &mut Board) -> HandleResult {
+        if self.current_target == Target::Stock {
+            return self.deal_or_reload(board);
+        }
+
+        let pile = board
+            .get(self.current_target)
+            .ok_or(MoveError::NoSuchPile)?;
+
+        if let Some(top_card) = pile.last() {
             if !top_card.is_visible() {
-                top_card.reveal();
-                return (true, None);
+                board.reveal(self.current_target)?;
+                return Ok((true, get_end_state(board, self.current_target)));
             }
 
             let new_state = TransferringState::new(self.current_target, self.num_selected_cards);
-            return (true, Some(Box::new(new_state)));
+            return Ok((true, Some(Box::new(new_state))));
         };
 
-        if self.current_target == Target::Stock {
-            board.reload_stock();
-            return (true, None);
-        }
-
-        (false, None)
+        Err(MoveError::EmptySource)
     }
 
     fn maybe_increment_card_range(&mut self, board: &Board) -> bool {
@@ -67,15 +85,19 @@ impl SelectingState {
         }
     }
 
+    /**
+     * State to move to after playing a move, which may have taken selected cards away.
+     */
+    fn get_state_after_move(&self, board: &Board) -> Box<dyn GameState> {
+        get_end_state(board, self.current_target)
+            .unwrap_or_else(|| Box::new(SelectingState::new(self.current_target)))
+    }
+
     pub fn maybe_decrement_card_range(&mut self) -> bool {
         match self.current_target {
-            Target::Pile(_) => {
-                if self.num_selected_cards > 1 {
-                    self.num_selected_cards -= 1;
-                    true
-                } else {
-                    false
-                }
+            Target::Pile(_) if self.num_selected_cards > 1 => {
+                self.num_selected_cards -= 1;
+                true
             }
             _ => false,
         }
@@ -84,26 +106,110 @@ impl SelectingState {
 
 impl GameState for SelectingState {
     fn get_status_of(&self, target: Target) -> Option<TargetStatus> {
-        (target == self.current_target).then(|| TargetStatus::Current {
+        (target == self.current_target).then_some(TargetStatus::Current {
             num_cards: self.num_selected_cards,
         })
     }
 
-    fn handle(&mut self, action: Action) -> (bool, Option<Box<dyn GameState>>) {
+    fn get_cursor(&self) -> Option<Target> {
+        Some(self.current_target)
+    }
+
+    fn handle(&mut self, action: Action) -> HandleResult {
         match action {
             Action::TargetPrevious(board) => {
                 let target = board.get_previous_target(self.current_target);
-                (true, Some(Box::new(SelectingState::new(target))))
+                Ok((true, Some(Box::new(SelectingState::new(target)))))
             }
             Action::TargetNext(board) => {
                 let target = board.get_next_target(self.current_target);
-                (true, Some(Box::new(SelectingState::new(target))))
+                Ok((true, Some(Box::new(SelectingState::new(target)))))
             }
-            Action::IncreaseRange(board) => (self.maybe_increment_card_range(board), None),
-            Action::DecreaseRange => (self.maybe_decrement_card_range(), None),
+            Action::IncreaseRange(board) => Ok((self.maybe_increment_card_range(board), None)),
+            Action::DecreaseRange => Ok((self.maybe_decrement_card_range(), None)),
             Action::Act(board) => self.maybe_act(board),
-            Action::Build(board) => (board.maybe_move_to_a_foundation(self.current_target), None),
-            Action::Discard(board) => (board.maybe_move_top_stock_card_to_waste(), None),
+            Action::Build(board) => {
+                board.maybe_move_to_a_foundation(self.current_target)?;
+                Ok((true, Some(self.get_state_after_move(board))))
+            }
+            Action::Deal(board) => self.deal_or_reload(board),
+            Action::Undo(board) => {
+                board.undo()?;
+                Ok((
+                    true,
+                    Some(Box::new(SelectingState::new(self.current_target))),
+                ))
+            }
+            Action::Redo(board) => {
+                board.redo()?;
+                Ok((true, Some(self.get_state_after_move(board))))
+            }
+            Action::AutoComplete(board) => {
+                board.auto_complete_step()?;
+                Ok((true, Some(self.get_state_after_move(board))))
+            }
+            Action::Hint(board) => {
+                let new_state = HintingState::new(self.current_target, board)?;
+                Ok((true, Some(Box::new(new_state))))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * Select the king and queen of hearts, with every other card but the king of
+     * spades on the foundations.
+     */
+    fn select_two_cards() -> (SelectingState, Board) {
+        let board = "
+            stock: -
+            waste: -
+            foundations: AS 2S 3S 4S 5S 6S 7S 8S 9S 10S JS QS | AH 2H 3H 4H 5H 6H 7H 8H 9H 10H JH | AD 2D 3D 4D 5D 6D 7D 8D 9D 10D JD QD KD | AC 2C 3C 4C 5C 6C 7C 8C 9C 10C JC QC KC
+            tableau: KS | KH QH | - | - | - | - | -
+        "
+        .parse::<Board>()
+        .unwrap();
+
+        let mut state = SelectingState::new(Target::Pile(1));
+        state.handle(Action::IncreaseRange(&board)).unwrap();
+
+        (state, board)
+    }
+
+    fn get_num_selected_cards(state: &dyn GameState, target: Target) -> Option<usize> {
+        match state.get_status_of(target) {
+            Some(TargetStatus::Current { num_cards }) => Some(num_cards),
+            _ => None,
         }
     }
+
+    #[test]
+    fn selects_a_single_card_after_building() {
+        let (mut state, mut board) = select_two_cards();
+
+        let (_, new_state) = state.handle(Action::Build(&mut board)).unwrap();
+
+        assert_eq!(board.get(Target::Pile(1)).unwrap().len(), 1);
+        assert_eq!(
+            get_num_selected_cards(new_state.unwrap().as_ref(), Target::Pile(1)),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn selects_a_single_card_after_auto_completing() {
+        let (mut state, mut board) = select_two_cards();
+
+        let (_, new_state) = state.handle(Action::AutoComplete(&mut board)).unwrap();
+
+        assert_eq!(board.get(Target::Pile(1)).unwrap().len(), 1);
+        assert_eq!(
+            get_num_selected_cards(new_state.unwrap().as_ref(), Target::Pile(1)),
+            Some(1)
+        );
+    }
 }
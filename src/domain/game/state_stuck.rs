@@ -0,0 +1,50 @@
+use crate::domain::{GameOutcome, MoveError, Target};
+
+use super::{
+    state_base::{GameState, HandleResult},
+    state_selecting::SelectingState,
+    Action, TargetStatus,
+};
+
+/**
+ * State reached once no move can make progress, from which moves can still be undone and redone.
+ * The player may also dismiss it to keep playing.
+ */
+pub struct StuckState {
+    current_target: Target,
+}
+
+impl StuckState {
+    pub fn new(current_target: Target) -> Self {
+        Self { current_target }
+    }
+}
+
+impl GameState for StuckState {
+    fn get_status_of(&self, _target: Target) -> Option<TargetStatus> {
+        None
+    }
+
+    fn get_cursor(&self) -> Option<Target> {
+        Some(self.current_target)
+    }
+
+    fn get_outcome(&self) -> GameOutcome {
+        GameOutcome::Stuck
+    }
+
+    fn handle(&mut self, action: Action) -> HandleResult {
+        match action {
+            action @ (Action::Undo(_) | Action::Redo(_)) => {
+                let mut state = SelectingState::new(self.current_target);
+                let (changed, new_state) = state.handle(action)?;
+                Ok((changed, Some(new_state.unwrap_or_else(|| Box::new(state)))))
+            }
+            Action::TargetPrevious(_)
+            | Action::TargetNext(_)
+            | Action::IncreaseRange(_)
+            | Action::DecreaseRange => Ok((false, None)),
+            _ => Err(MoveError::NoMoveAvailable),
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use crate::domain::Target;
 
-use super::{state_base::GameState, state_selecting::SelectingState, Action, TargetStatus};
+use super::{
+    state_base::{get_end_state, GameState, HandleResult},
+    state_selecting::SelectingState,
+    Action, TargetStatus,
+};
 
 pub struct TransferringState {
     current_target: Target,
@@ -33,27 +37,55 @@ impl GameState for TransferringState {
         None
     }
 
-    fn handle(&mut self, action: Action) -> (bool, Option<Box<dyn GameState>>) {
+    fn get_cursor(&self) -> Option<Target> {
+        Some(self.current_target)
+    }
+
+    fn handle(&mut self, action: Action) -> HandleResult {
         match action {
             Action::TargetPrevious(board) => {
                 self.current_target = board.get_previous_target(self.current_target);
-                (true, None)
+                Ok((true, None))
             }
             Action::TargetNext(board) => {
                 self.current_target = board.get_next_target(self.current_target);
-                (true, None)
+                Ok((true, None))
             }
             Action::Act(board) => {
-                board.maybe_transfer(
-                    self.picked_target,
-                    self.current_target,
-                    self.num_picked_cards,
-                );
-
-                let new_state = SelectingState::new(self.current_target);
-                (true, Some(Box::new(new_state)))
+                // Acting on the picked pile again puts the cards back down.
+                if self.current_target != self.picked_target {
+                    board.maybe_transfer(
+                        self.picked_target,
+                        self.current_target,
+                        self.num_picked_cards,
+                    )?;
+                }
+
+                let new_state = get_end_state(board, self.current_target)
+                    .unwrap_or_else(|| Box::new(SelectingState::new(self.current_target)));
+                Ok((true, Some(new_state)))
+            }
+            Action::Undo(board) => {
+                board.undo()?;
+                Ok((
+                    true,
+                    Some(Box::new(SelectingState::new(self.current_target))),
+                ))
+            }
+            Action::Redo(board) => {
+                board.redo()?;
+                let new_state = get_end_state(board, self.current_target)
+                    .unwrap_or_else(|| Box::new(SelectingState::new(self.current_target)));
+                Ok((true, Some(new_state)))
+            }
+            Action::AutoComplete(board) => {
+                // The picked cards are put back down.
+                board.auto_complete_step()?;
+                let new_state = get_end_state(board, self.current_target)
+                    .unwrap_or_else(|| Box::new(SelectingState::new(self.current_target)));
+                Ok((true, Some(new_state)))
             }
-            _ => (false, None),
+            _ => Ok((false, None)),
         }
     }
 }
@@ -0,0 +1,35 @@
+use crate::domain::{GameOutcome, MoveError, Target};
+
+use super::{
+    state_base::{GameState, HandleResult},
+    Action, TargetStatus,
+};
+
+/**
+ * Terminal state reached once all cards are on the foundations.
+ */
+pub struct WonState;
+
+impl GameState for WonState {
+    fn get_status_of(&self, _target: Target) -> Option<TargetStatus> {
+        None
+    }
+
+    fn get_cursor(&self) -> Option<Target> {
+        None
+    }
+
+    fn get_outcome(&self) -> GameOutcome {
+        GameOutcome::Won
+    }
+
+    fn handle(&mut self, action: Action) -> HandleResult {
+        match action {
+            Action::TargetPrevious(_)
+            | Action::TargetNext(_)
+            | Action::IncreaseRange(_)
+            | Action::DecreaseRange => Ok((false, None)),
+            _ => Err(MoveError::GameOver),
+        }
+    }
+}
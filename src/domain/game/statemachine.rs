@@ -1,35 +1,346 @@
+use std::time::Duration;
+
 use super::{
-    super::Target, state_base::GameState, state_selecting::SelectingState, Action, TargetStatus,
+    super::{Board, Clock, GameOutcome, Move, MoveError, Target},
+    state_base::{get_end_state, GameState},
+    state_replaying::ReplayingState,
+    state_selecting::SelectingState,
+    Action, TargetStatus,
 };
 
 pub struct StateMachine {
     current_state: Box<dyn GameState>,
+    // Number of moves played, not counting undoing and redoing them.
+    num_moves: u32,
+    clock: Clock,
+    paused: bool,
+    autoplay: bool,
+    // Whether the clock should start again once the game is resumed.
+    clock_was_running: bool,
+    // Whether the player chose to keep playing once told that the game is stuck,
+    // until a move leads to a position which isn't.
+    stuck_dismissed: bool,
 }
 
 impl Default for StateMachine {
     fn default() -> Self {
-        Self {
-            current_state: Box::new(SelectingState::default()),
-        }
+        Self::with_state(Box::new(SelectingState::default()))
     }
 }
 
 impl StateMachine {
+    fn with_state(state: Box<dyn GameState>) -> Self {
+        Self {
+            current_state: state,
+            num_moves: 0,
+            clock: Clock::default(),
+            paused: false,
+            autoplay: false,
+            clock_was_running: false,
+            stuck_dismissed: false,
+        }
+    }
+
+    /**
+     * Carry on with a game, e.g. a saved one, with the cursor on the given target.
+     * The clock starts again on the next move.
+     */
+    pub fn restore(cursor: Target, num_moves: u32, elapsed: Duration) -> Self {
+        Self {
+            num_moves,
+            clock: Clock::new(elapsed),
+            ..Self::with_state(Box::new(SelectingState::new(cursor)))
+        }
+    }
+
+    /**
+     * Watch a recorded game, highlighting the move which led to the current position.
+     */
+    pub fn replaying(last_move: Option<Move>) -> Self {
+        Self::with_state(Box::new(ReplayingState::new(last_move)))
+    }
+
+    /**
+     * Move cards to the foundations after each move, whenever it is safe to do so.
+     */
+    pub fn autoplay(mut self, autoplay: bool) -> Self {
+        self.autoplay = autoplay;
+        self
+    }
+
+    pub fn get_num_moves(&self) -> u32 {
+        self.num_moves
+    }
+
+    /**
+     * Time spent playing, from the first move on and not counting pauses.
+     */
+    pub fn get_elapsed(&self) -> Duration {
+        self.clock.get_elapsed()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /**
+     * Stop the clock and ignore actions until the game is resumed.
+     */
+    pub fn pause(&mut self) {
+        if !self.paused {
+            self.paused = true;
+            self.clock_was_running = self.clock.is_running();
+            self.clock.stop();
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if self.paused {
+            self.paused = false;
+            if self.clock_was_running {
+                self.clock.start();
+            }
+        }
+    }
+
+    pub fn get_cursor(&self) -> Option<Target> {
+        self.current_state.get_cursor()
+    }
+
+    /**
+     * Whether the game goes on, is won, or can't make progress anymore.
+     */
+    pub fn get_outcome(&self) -> GameOutcome {
+        self.current_state.get_outcome()
+    }
+
+    /**
+     * Keep playing a game which is stuck, e.g. to look around or try moves anyway.
+     * The game isn't reported as stuck anymore.
+     */
+    pub fn dismiss_stuck(&mut self) {
+        if self.get_outcome() == GameOutcome::Stuck {
+            self.stuck_dismissed = true;
+            let cursor = self.get_cursor().unwrap_or(Target::Stock);
+            self.move_to(Box::new(SelectingState::new(cursor)));
+        }
+    }
+
     pub fn get_status_of(&self, target: Target) -> Option<TargetStatus> {
         self.current_state.get_status_of(target)
     }
 
-    pub fn handle(&mut self, action: Action) -> bool {
-        let (changed, new_state) = self.current_state.handle(action);
+    pub fn handle(&mut self, mut action: Action) -> Result<bool, MoveError> {
+        if self.paused {
+            return Ok(false);
+        }
+
+        let history_len = get_history_len(action.get_board());
+        let (changed, new_state) = self.current_state.handle(action.reborrow())?;
+
+        // Each move played, undone or redone changes the length of the history.
+        let new_history_len = get_history_len(action.get_board());
+
+        // Undoing a move isn't checked for being stuck, so it also counts as getting unstuck.
+        let is_stuck =
+            matches!(&new_state, Some(state) if state.get_outcome() == GameOutcome::Stuck);
+
+        if new_history_len != history_len && !is_stuck {
+            self.stuck_dismissed = false;
+        }
 
         if let Some(new_state) = new_state {
             self.move_to(new_state);
         }
 
-        changed
+        if new_history_len != history_len {
+            self.clock.start();
+        }
+
+        let is_new_move = new_history_len > history_len && !matches!(action, Action::Redo(_));
+
+        if is_new_move {
+            self.num_moves += 1;
+        }
+
+        // Redone moves already include the cards that were autoplayed along with them.
+        if self.autoplay && is_new_move {
+            self.autoplay_after_move(action.get_board_mut());
+        }
+
+        if let Some(board) = action.get_board() {
+            if board.get_outcome() == GameOutcome::Won {
+                self.clock.stop();
+            }
+        }
+
+        Ok(changed)
+    }
+
+    fn autoplay_after_move(&mut self, board: Option<&mut Board>) {
+        let board = match board {
+            Some(board) => board,
+            None => return,
+        };
+
+        if board.autoplay() == 0 {
+            return;
+        }
+
+        let cursor = self.get_cursor().unwrap_or(Target::Stock);
+
+        // Cards may have left the selected pile, so the selection starts over.
+        let new_state =
+            get_end_state(board, cursor).unwrap_or_else(|| Box::new(SelectingState::new(cursor)));
+        self.move_to(new_state);
     }
 
     pub fn move_to(&mut self, new_state: Box<dyn GameState>) {
+        // Once the player chose to keep playing, being stuck isn't brought up again.
+        if self.stuck_dismissed && new_state.get_outcome() == GameOutcome::Stuck {
+            let cursor = new_state.get_cursor().unwrap_or(Target::Stock);
+            self.current_state = Box::new(SelectingState::new(cursor));
+            return;
+        }
+
         self.current_state = new_state;
     }
 }
+
+fn get_history_len(board: Option<&Board>) -> Option<usize> {
+    board.map(|board| board.get_history().len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_new_moves_only() {
+        let mut board = Board::from_seed(1);
+        let mut state_machine = StateMachine::default();
+
+        state_machine.handle(Action::Deal(&mut board)).unwrap();
+        state_machine.handle(Action::Deal(&mut board)).unwrap();
+        state_machine.handle(Action::Undo(&mut board)).unwrap();
+        state_machine.handle(Action::Redo(&mut board)).unwrap();
+
+        assert_eq!(state_machine.get_num_moves(), 2);
+
+        state_machine.handle(Action::Undo(&mut board)).unwrap();
+        state_machine.handle(Action::Deal(&mut board)).unwrap();
+
+        assert_eq!(state_machine.get_num_moves(), 3);
+    }
+
+    #[test]
+    fn ignores_actions_while_paused() {
+        let mut board = Board::from_seed(1);
+        let mut state_machine = StateMachine::default();
+
+        state_machine.pause();
+
+        assert_eq!(state_machine.handle(Action::Deal(&mut board)), Ok(false));
+        assert!(board.get_history().is_empty());
+        assert_eq!(state_machine.get_num_moves(), 0);
+
+        state_machine.resume();
+        state_machine.handle(Action::Deal(&mut board)).unwrap();
+
+        assert_eq!(state_machine.get_num_moves(), 1);
+    }
+
+    #[test]
+    fn stops_the_clock_while_paused() {
+        let mut board = Board::from_seed(1);
+        let mut state_machine = StateMachine::restore(Target::Stock, 3, Duration::from_secs(60));
+
+        // The clock only starts again with the next move.
+        state_machine.pause();
+        state_machine.resume();
+        std::thread::sleep(Duration::from_millis(10));
+
+        assert_eq!(state_machine.get_elapsed(), Duration::from_secs(60));
+
+        state_machine.handle(Action::Deal(&mut board)).unwrap();
+        state_machine.pause();
+        let elapsed = state_machine.get_elapsed();
+        std::thread::sleep(Duration::from_millis(10));
+
+        assert_eq!(state_machine.get_elapsed(), elapsed);
+
+        state_machine.resume();
+        std::thread::sleep(Duration::from_millis(10));
+
+        assert!(state_machine.get_elapsed() > elapsed);
+    }
+
+    #[test]
+    fn tells_again_when_stuck_after_undoing() {
+        // The queen of clubs covers the jack, and both red kings are under them.
+        let mut board = "
+            stock: -
+            waste: -
+            foundations: AS 2S 3S 4S 5S 6S 7S 8S 9S 10S JS QS KS | AH 2H 3H 4H 5H 6H 7H 8H 9H 10H JH QH | AD 2D 3D 4D 5D 6D 7D 8D 9D 10D JD QD | AC 2C 3C 4C 5C 6C 7C 8C 9C 10C
+            tableau: KH KD JC QC | KC | - | - | - | - | -
+        "
+        .parse::<Board>()
+        .unwrap();
+        let mut state_machine = StateMachine::default();
+
+        // Move the king of clubs to the next pile.
+        for _ in 0..3 {
+            state_machine.handle(Action::TargetNext(&board)).unwrap();
+        }
+        state_machine.handle(Action::Act(&mut board)).unwrap();
+        state_machine.handle(Action::TargetNext(&board)).unwrap();
+        state_machine.handle(Action::Act(&mut board)).unwrap();
+
+        assert_eq!(state_machine.get_outcome(), GameOutcome::Stuck);
+
+        // Moving it back leaves the game just as stuck, which isn't brought up again.
+        state_machine.dismiss_stuck();
+        state_machine.handle(Action::Act(&mut board)).unwrap();
+        state_machine
+            .handle(Action::TargetPrevious(&board))
+            .unwrap();
+        state_machine.handle(Action::Act(&mut board)).unwrap();
+
+        assert_eq!(board.get_history().len(), 2);
+        assert_eq!(state_machine.get_outcome(), GameOutcome::InProgress);
+
+        state_machine.handle(Action::Undo(&mut board)).unwrap();
+        state_machine.handle(Action::Redo(&mut board)).unwrap();
+
+        assert_eq!(state_machine.get_outcome(), GameOutcome::Stuck);
+    }
+
+    #[test]
+    fn autoplays_after_new_moves_only() {
+        // The fifth card dealt from deal 1 is the ace of hearts, and the 2 of hearts
+        // is on top of the sixth pile.
+        let mut board = Board::from_seed(1);
+        let mut state_machine = StateMachine::default().autoplay(true);
+
+        for _ in 0..5 {
+            state_machine.handle(Action::Deal(&mut board)).unwrap();
+        }
+
+        assert_eq!(board.get_foundations()[0].len(), 2);
+
+        // Moves played without autoplay are redone as they were played.
+        let mut board = Board::from_seed(1);
+        let mut state_machine = StateMachine::default();
+
+        for _ in 0..5 {
+            state_machine.handle(Action::Deal(&mut board)).unwrap();
+        }
+        state_machine.handle(Action::Undo(&mut board)).unwrap();
+
+        let mut state_machine = state_machine.autoplay(true);
+        state_machine.handle(Action::Redo(&mut board)).unwrap();
+
+        assert!(board.get_foundations()[0].is_empty());
+        assert_eq!(board.get_waste().last().unwrap().to_string(), "AH");
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::HashSet;
+
+use super::{Board, Move, Rank, Target};
+
+/**
+ * List moves worth playing from the current position, best first.
+ *
+ * Moves that can't make progress, such as moving a run between piles
+ * without uncovering anything, are left out.
+ */
+pub fn get_hints(board: &Board) -> Vec<Move> {
+    // Moving the same cards to either of several empty piles is a single suggestion.
+    let mut hints = board
+        .distinct_moves()
+        .filter_map(|mv| get_priority(board, mv).map(|priority| (priority, mv)))
+        .collect::<Vec<_>>();
+
+    // Sorting is stable, so moves of equal priority keep the board's order.
+    hints.sort_by_key(|&(priority, _)| std::cmp::Reverse(priority));
+
+    hints.into_iter().map(|(_, mv)| mv).collect()
+}
+
+/**
+ * Most moves to try when looking for progress, so that checking whether the game is
+ * stuck doesn't hold up play. Running out of moves counts as being able to make progress.
+ */
+const MAX_STUCK_SEARCH: usize = 5_000;
+
+/**
+ * Whether no sequence of moves can make progress anymore, however the stock is dealt.
+ *
+ * Dealing from the stock doesn't change the tableau and the foundations, and moving
+ * cards between them doesn't change the stock, so every arrangement of the tableau and
+ * foundations reachable without making progress is tried with every card the stock
+ * can bring to the top of the waste.
+ */
+pub fn is_stuck(board: &Board) -> bool {
+    if board.is_won() {
+        return false;
+    }
+
+    let start = Progress::of(board);
+    let mut deals = get_stock_deals(board);
+
+    // Looking a single move ahead is usually enough, and much faster.
+    if deals.iter_mut().any(|board| can_progress_now(board, start)) {
+        return false;
+    }
+
+    let mut budget = MAX_STUCK_SEARCH;
+
+    !deals
+        .iter_mut()
+        .any(|board| can_progress(board, start, &mut budget))
+}
+
+/**
+ * What playing the game improves: turning over cards, playing cards from the stock and
+ * the waste, and building up the foundations beyond where they stand.
+ */
+#[derive(Debug, Clone, Copy)]
+struct Progress {
+    num_hidden: usize,
+    num_in_stock: usize,
+    num_founded: usize,
+}
+
+impl Progress {
+    fn of(board: &Board) -> Self {
+        Self {
+            num_hidden: board
+                .get_tableau()
+                .iter()
+                .flatten()
+                .filter(|card| !card.is_visible())
+                .count(),
+            num_in_stock: board.get_stock().len() + board.get_waste().len(),
+            num_founded: board.get_foundations().iter().map(Vec::len).sum(),
+        }
+    }
+
+    fn is_beyond(&self, other: Self) -> bool {
+        self.num_hidden < other.num_hidden
+            || self.num_in_stock < other.num_in_stock
+            || self.num_founded > other.num_founded
+    }
+}
+
+/**
+ * Positions reached by dealing through the stock, starting with the current one.
+ * Cycling the stock without playing anything from it always comes back to the same
+ * cards, so it is dealt through at most once.
+ */
+fn get_stock_deals(board: &Board) -> Vec<Board> {
+    let mut board = board.clone();
+    let mut deals = Vec::new();
+    // The cards of the stock and waste keep their order, so their sizes tell where the cycle is.
+    let mut seen = HashSet::new();
+
+    while seen.insert((board.get_stock().len(), board.get_waste().len())) {
+        deals.push(board.clone());
+
+        let mv = if board.get_stock().is_empty() {
+            Move::Reload
+        } else {
+            Move::Deal
+        };
+
+        if board.apply(mv).is_err() {
+            break;
+        }
+    }
+
+    deals
+}
+
+/**
+ * Whether a single move, other than dealing from the stock, makes progress.
+ */
+fn can_progress_now(board: &mut Board, start: Progress) -> bool {
+    let moves = get_moves(board);
+
+    moves.into_iter().any(|mv| {
+        board.apply(mv).unwrap();
+        let is_progress = Progress::of(board).is_beyond(start);
+        board.undo().unwrap();
+        is_progress
+    })
+}
+
+/**
+ * Whether progress can be made without touching the stock, trying every arrangement
+ * of the tableau and foundations reachable without making progress, depth first.
+ * Each move tried uses up the budget, and the board is left as it was.
+ */
+fn can_progress(board: &mut Board, start: Progress, budget: &mut usize) -> bool {
+    let mut seen = HashSet::from([board.get_position_key()]);
+    let mut stack = vec![get_moves(board)];
+
+    let found = loop {
+        let moves = match stack.last_mut() {
+            Some(moves) => moves,
+            None => break false,
+        };
+
+        let mv = match moves.pop() {
+            Some(mv) => mv,
+            None => {
+                // All moves were tried from this position: backtrack.
+                stack.pop();
+                if !stack.is_empty() {
+                    board.undo().unwrap();
+                }
+                continue;
+            }
+        };
+
+        if *budget == 0 {
+            break true;
+        }
+        *budget -= 1;
+
+        board.apply(mv).unwrap();
+
+        if Progress::of(board).is_beyond(start) {
+            // Undo this move below, along with those leading to it.
+            stack.push(Vec::new());
+            break true;
+        }
+
+        if !seen.insert(board.get_position_key()) {
+            board.undo().unwrap();
+            continue;
+        }
+
+        stack.push(get_moves(board));
+    };
+
+    // Take back the moves leading to the current position.
+    for _ in 1..stack.len() {
+        board.undo().unwrap();
+    }
+
+    found
+}
+
+fn get_moves(board: &Board) -> Vec<Move> {
+    board
+        .distinct_moves()
+        .filter(|mv| !matches!(mv, Move::Deal | Move::Reload))
+        .collect()
+}
+
+/**
+ * How promising a move is, higher first, or `None` if it can't make progress on its own.
+ */
+pub(crate) fn get_priority(board: &Board, mv: Move) -> Option<u32> {
+    match mv {
+        Move::Reveal { .. } => Some(100),
+        Move::Transfer {
+            dest: Target::Foundation(_),
+            ..
+        } => Some(90),
+        Move::Transfer {
+            source: source @ Target::Pile(_),
+            dest: Target::Pile(_),
+            ..
+        } => {
+            match board.get_card_left_by(mv) {
+                // Uncovers a face down card.
+                Some(card) if !card.is_visible() => Some(80),
+                // Moves a king which already sits at the bottom of its pile.
+                None if board.get(source).unwrap()[0].rank == Rank::KING => None,
+                // Empties a pile, making room for a king.
+                None => Some(70),
+                _ => None,
+            }
+        }
+        Move::Transfer {
+            source: Target::Waste,
+            dest: dest @ Target::Pile(_),
+            ..
+        } => {
+            let pile = board.get(dest).unwrap();
+
+            match pile.last() {
+                // Moves a king to an empty pile, where it can start a new run.
+                None => Some(75),
+                Some(_) => Some(50),
+            }
+        }
+        Move::Deal => Some(10),
+        Move::Reload => Some(5),
+        // Moving cards back down from the foundations rarely helps.
+        Move::Transfer { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{DrawMode, Rules, Variant};
+
+    #[test]
+    fn finds_progress_several_moves_ahead() {
+        // Moving the 4 and 3 of diamonds down from the foundations lets the 2 of clubs go
+        // onto the 3, uncovering the 10 of hearts.
+        let board = Board::from_position(
+            "
+            stock: ?7H ?6C ?5C ?9H ?JH ?4S ?QH ?3S ?QC ?JS ?6D
+            waste: -
+            foundations: AD 2D 3D 4D | - | AH 2H 3H 4H 5H | AS 2S
+            tableau: - | KD QS JD 10C 9D | ?AC 7D 6S 5D 4C | ?KC 9C 8H 7C 6H 5S | ?8S ?KH 10S | ?3C ?8C ?10H 2C | KS QD JC 10D 9S 8D 7S
+            ",
+            Rules::default().draw_mode(DrawMode::Three),
+        )
+        .unwrap();
+
+        assert!(!is_stuck(&board));
+    }
+
+    #[test]
+    fn finds_stuck_positions() {
+        // The stock was dealt through the only pass allowed, and none of the cards left
+        // in the waste or on top of the tableau can go anywhere useful.
+        let board = Board::from_position(
+            "
+            stock: -
+            waste: 10C JH JC 2C KC 9H QC 4C 3S 3H JD 5H 9D QD 6H 7S
+            foundations: AD | AC | AS 2S | AH
+            tableau: 5S 4H 3C | KS QH | 4S 3D | ?QS ?6S ?10S 9S 8D 7C 6D 5C | ?2D ?9C ?8H 7H 6C | ?4D ?5D ?JS ?2H ?10H 7D | ?8S ?10D ?KH ?8C KD
+            ",
+            Rules::for_variant(Variant::Vegas, DrawMode::One),
+        )
+        .unwrap();
+
+        assert!(is_stuck(&board));
+    }
+}
@@ -1,5 +1,16 @@
+mod clock;
 mod entities;
 mod game;
+mod hints;
+mod record;
+mod replay;
+mod scoring;
+pub mod solver;
 
+pub use clock::*;
 pub use entities::*;
 pub use game::*;
+pub use hints::*;
+pub use record::*;
+pub use replay::*;
+pub use scoring::*;
@@ -0,0 +1,334 @@
+use std::{error::Error, fmt};
+
+use super::{
+    Board, Command, DrawMode, InvalidRule, Move, MoveError, Passes, PositionError, Reveal, Rules,
+    Target, Variant,
+};
+
+const MOVES_PER_LINE: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordError {
+    InvalidLine(String),
+    MissingDeal,
+    InvalidRule(InvalidRule),
+    InvalidPosition(PositionError),
+    /**
+     * The move at this index, starting from 0, isn't written in move notation.
+     */
+    InvalidMove {
+        index: usize,
+        notation: String,
+    },
+    /**
+     * The move at this index, starting from 0, can't be played.
+     */
+    IllegalMove {
+        index: usize,
+        notation: String,
+        error: MoveError,
+    },
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::InvalidLine(line) => write!(f, "Unexpected line '{line}'"),
+            RecordError::MissingDeal => write!(f, "The deal or initial position is missing"),
+            RecordError::InvalidRule(err) => write!(f, "{err}"),
+            RecordError::InvalidPosition(err) => write!(f, "{err}"),
+            RecordError::InvalidMove { index, notation } => {
+                write!(f, "Move {} '{notation}' is not a move", index + 1)
+            }
+            RecordError::IllegalMove {
+                index,
+                notation,
+                error,
+            } => write!(
+                f,
+                "Move {} '{notation}' can't be played: {error}",
+                index + 1
+            ),
+        }
+    }
+}
+
+impl Error for RecordError {}
+
+impl From<InvalidRule> for RecordError {
+    fn from(err: InvalidRule) -> Self {
+        RecordError::InvalidRule(err)
+    }
+}
+
+impl From<PositionError> for RecordError {
+    fn from(err: PositionError) -> Self {
+        RecordError::InvalidPosition(err)
+    }
+}
+
+/**
+ * Write down a game, finished or not, as its rules and initial deal followed
+ * by the moves played so far:
+ *
+ * ```text
+ * variant: klondike
+ * draw: 1
+ * passes: unlimited
+ * reveal: manual
+ * deal: 1234
+ * moves: S 3>5x2 ^3 W>F1
+ * ```
+ *
+ * Games which didn't start from a numbered deal list their initial position
+ * in position notation instead of the `deal` line.
+ */
+pub fn export_game(board: &Board) -> String {
+    let rules = board.get_rules();
+
+    let mut record = format!(
+        "variant: {}\ndraw: {}\npasses: {}\nreveal: {}\n",
+        rules.variant, rules.draw_mode, rules.passes, rules.reveal
+    );
+
+    match board.get_deal_number() {
+        Some(deal_number) => record += &format!("deal: {deal_number}\n"),
+        None => record += &board.get_initial_position().to_string(),
+    }
+
+    // Cards turned over automatically along with a move are turned over again on import.
+    let is_implied = |index: usize, command: &Command| {
+        rules.reveal == Reveal::Auto && index > 0 && matches!(command, Command::Reveal { .. })
+    };
+
+    let moves = board
+        .get_history()
+        .iter()
+        .flat_map(|commands| commands.iter().enumerate())
+        .filter(|(index, command)| !is_implied(*index, command))
+        .map(|(_, &command)| Move::from(command).to_string())
+        .collect::<Vec<_>>();
+
+    for line in moves.chunks(MOVES_PER_LINE) {
+        record += &format!("moves: {}\n", line.join(" "));
+    }
+
+    record
+}
+
+/**
+ * Replay a game written down by `export_game()`, so that its moves can be undone.
+ */
+pub fn import_game(text: &str) -> Result<Board, RecordError> {
+    let mut variant = Variant::default();
+    let mut draw_mode = DrawMode::default();
+    let mut passes = None;
+    let mut reveal = Reveal::default();
+    let mut deal_number = None;
+    let mut position = String::new();
+    let mut moves = Vec::new();
+
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let invalid = || RecordError::InvalidLine(line.to_string());
+
+        let (name, value) = line.split_once(':').ok_or_else(invalid)?;
+        let value = value.trim();
+
+        match name.trim() {
+            "variant" => variant = value.parse()?,
+            "draw" => draw_mode = value.parse()?,
+            "passes" => passes = Some(value.parse::<Passes>()?),
+            "reveal" => reveal = value.parse()?,
+            "deal" => deal_number = Some(value.parse::<u64>().map_err(|_| invalid())?),
+            "stock" | "waste" | "foundations" | "tableau" => {
+                position += line;
+                position += "\n";
+            }
+            "moves" => moves.extend(value.split_whitespace()),
+            _ => return Err(invalid()),
+        }
+    }
+
+    let mut rules = Rules::for_variant(variant, draw_mode).reveal(reveal);
+
+    if let Some(passes) = passes {
+        rules = rules.passes(passes);
+    }
+
+    let mut board = match deal_number {
+        Some(_) if !position.is_empty() => {
+            return Err(RecordError::InvalidLine(position.trim().to_string()))
+        }
+        Some(deal_number) => Board::deal(deal_number, rules),
+        None if position.is_empty() => return Err(RecordError::MissingDeal),
+        None => Board::from_position(&position, rules)?,
+    };
+
+    for (index, notation) in moves.into_iter().enumerate() {
+        let mv = parse_move(notation, &board).ok_or_else(|| RecordError::InvalidMove {
+            index,
+            notation: notation.to_string(),
+        })?;
+
+        board.apply(mv).map_err(|error| RecordError::IllegalMove {
+            index,
+            notation: notation.to_string(),
+            error,
+        })?;
+    }
+
+    Ok(board)
+}
+
+/**
+ * Read a move written in move notation, as it would be played on the board.
+ *
+ * `S` deals from the stock, or reloads it when empty, and a move to `F`
+ * goes to whichever foundation accepts the card.
+ */
+pub fn parse_move(notation: &str, board: &Board) -> Option<Move> {
+    if notation == "S" {
+        let mv = if board.get_stock().is_empty() {
+            Move::Reload
+        } else {
+            Move::Deal
+        };
+
+        return Some(mv);
+    }
+
+    if let Some(pile) = notation.strip_prefix('^') {
+        return match pile.parse().ok()? {
+            target @ Target::Pile(_) => Some(Move::Reveal { target }),
+            _ => None,
+        };
+    }
+
+    let (source, rest) = notation.split_once('>')?;
+
+    let (dest, num_cards) = match rest.split_once('x') {
+        Some((dest, num_cards)) => (dest, num_cards.parse().ok().filter(|&n| n > 0)?),
+        None => (rest, 1),
+    };
+
+    let source = source.parse().ok()?;
+
+    if dest == "F" {
+        let to_foundation = |index| Move::Transfer {
+            source,
+            dest: Target::Foundation(index),
+            num_cards,
+        };
+
+        // Fall back to the first foundation, so that playing the move explains what's wrong.
+        let mv = (0..board.get_foundations().len())
+            .map(to_foundation)
+            .find(|&mv| board.check_move(mv).is_ok())
+            .unwrap_or_else(|| to_foundation(0));
+
+        return Some(mv);
+    }
+
+    Some(Move::Transfer {
+        source,
+        dest: dest.parse().ok()?,
+        num_cards,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::DealRng;
+
+    /**
+     * Play random moves from a deal, moving cards to the foundations after each
+     * one as the game does with autoplay on.
+     */
+    fn play_randomly(deal_number: u64, rules: Rules, autoplay: bool) -> Board {
+        let mut board = Board::deal(deal_number, rules);
+        let mut rng = DealRng::new(deal_number);
+
+        for _ in 0..150 {
+            let moves = board.legal_moves().collect::<Vec<_>>();
+
+            if moves.is_empty() {
+                break;
+            }
+
+            board.apply(moves[rng.next_below(moves.len())]).unwrap();
+
+            if autoplay {
+                board.autoplay();
+            }
+        }
+
+        board
+    }
+
+    #[test]
+    fn reads_back_exported_games() {
+        let games = [
+            (Rules::default(), false),
+            (Rules::default().draw_mode(DrawMode::Three), false),
+            (Rules::default().reveal(Reveal::Auto), false),
+            (Rules::default(), true),
+            (Rules::default().reveal(Reveal::Auto), true),
+        ];
+
+        for (deal_number, (rules, autoplay)) in (0..).zip(games) {
+            let board = play_randomly(deal_number, rules, autoplay);
+            let record = export_game(&board);
+
+            let imported = import_game(&record).unwrap();
+
+            assert_eq!(imported.to_string(), board.to_string(), "{record}");
+            assert_eq!(imported.get_pass(), board.get_pass());
+            assert_eq!(imported.get_rules(), board.get_rules());
+            assert_eq!(export_game(&imported), record);
+        }
+    }
+
+    #[test]
+    fn reads_back_games_started_from_a_position() {
+        let position = Board::from_seed(5).to_string();
+        let mut board = Board::from_position(&position, Rules::default()).unwrap();
+
+        board.deal_from_stock().unwrap();
+        board.deal_from_stock().unwrap();
+
+        let imported = import_game(&export_game(&board)).unwrap();
+
+        assert_eq!(imported.to_string(), board.to_string());
+        assert_eq!(imported.get_deal_number(), None);
+        assert_eq!(imported.get_initial_position().to_string(), position);
+    }
+
+    #[test]
+    fn tells_which_move_is_illegal() {
+        // The 4 of spades can't go on the 8 of diamonds.
+        let record = "variant: klondike\ndraw: 1\npasses: unlimited\nreveal: manual\ndeal: 1\nmoves: S S 1>3 S\n";
+
+        assert_eq!(
+            import_game(record).unwrap_err(),
+            RecordError::IllegalMove {
+                index: 2,
+                notation: "1>3".to_string(),
+                error: MoveError::RankMismatch,
+            }
+        );
+    }
+
+    #[test]
+    fn tells_which_move_is_not_a_move() {
+        let record = "variant: klondike\ndraw: 1\npasses: unlimited\nreveal: manual\ndeal: 1\nmoves: S 1-3 S\n";
+
+        assert_eq!(
+            import_game(record).unwrap_err(),
+            RecordError::InvalidMove {
+                index: 1,
+                notation: "1-3".to_string(),
+            }
+        );
+    }
+}
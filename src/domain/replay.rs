@@ -0,0 +1,90 @@
+use super::{Board, Move};
+
+/**
+ * Step back and forth through the moves of a game, e.g. one imported from a game record.
+ */
+pub struct Replay {
+    board: Board,
+    num_moves: usize,
+    index: usize,
+}
+
+impl Replay {
+    /**
+     * Replay the moves played on a board, starting from its initial position.
+     */
+    pub fn new(board: &Board) -> Self {
+        let mut board = board.clone();
+        let num_moves = board.get_history().iter().count();
+
+        // Undone moves are redone in the order they were played.
+        while board.undo().is_ok() {}
+
+        Self {
+            board,
+            num_moves,
+            index: 0,
+        }
+    }
+
+    pub fn get_board(&self) -> &Board {
+        &self.board
+    }
+
+    /**
+     * Number of moves played so far.
+     */
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    pub fn get_num_moves(&self) -> usize {
+        self.num_moves
+    }
+
+    /**
+     * The move which led to the current position, if any.
+     */
+    pub fn get_last_move(&self) -> Option<Move> {
+        let commands = self.board.get_history().iter().last()?;
+        commands.first().map(|&command| Move::from(command))
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.index == self.num_moves
+    }
+
+    pub fn step_forward(&mut self) -> bool {
+        if self.is_at_end() || self.board.redo().is_err() {
+            return false;
+        }
+
+        self.index += 1;
+        true
+    }
+
+    pub fn step_back(&mut self) -> bool {
+        if self.index == 0 || self.board.undo().is_err() {
+            return false;
+        }
+
+        self.index -= 1;
+        true
+    }
+
+    pub fn jump_to_start(&mut self) -> bool {
+        let mut changed = false;
+        while self.step_back() {
+            changed = true;
+        }
+        changed
+    }
+
+    pub fn jump_to_end(&mut self) -> bool {
+        let mut changed = false;
+        while self.step_forward() {
+            changed = true;
+        }
+        changed
+    }
+}
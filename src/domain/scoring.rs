@@ -0,0 +1,249 @@
+use std::time::Duration;
+
+use super::{Board, Command, DrawMode, Target};
+
+/**
+ * Price of a deal under Vegas scoring.
+ */
+pub const VEGAS_ANTE: i64 = 52;
+
+/**
+ * Amount won for each card put on a foundation under Vegas scoring.
+ */
+pub const VEGAS_CARD_VALUE: i64 = 5;
+
+/**
+ * Games won faster than this don't earn a time bonus, which would be huge otherwise.
+ */
+const MIN_BONUS_TIME: Duration = Duration::from_secs(30);
+
+/**
+ * Score of the game so far under standard Klondike scoring.
+ *
+ * The score is computed from the moves played, so that undoing a move also
+ * takes back its points. It never goes below zero.
+ */
+pub fn get_score(board: &Board) -> u32 {
+    let draw_mode = board.get_rules().draw_mode;
+
+    let score = board
+        .get_history()
+        .iter()
+        .flatten()
+        .fold(0, |score, &command| {
+            (score + get_points(command, draw_mode)).max(0)
+        });
+
+    score as u32
+}
+
+/**
+ * Points earned or lost by playing a command.
+ */
+fn get_points(command: Command, draw_mode: DrawMode) -> i32 {
+    match command {
+        Command::Transfer { source, dest, .. } => match (source, dest) {
+            (Target::Foundation(_), Target::Foundation(_)) => 0,
+            (_, Target::Foundation(_)) => 10,
+            (Target::Foundation(_), _) => -15,
+            (Target::Waste, Target::Pile(_)) => 5,
+            _ => 0,
+        },
+        Command::Reveal { .. } => 5,
+        Command::Reload if draw_mode == DrawMode::One => -100,
+        Command::Deal { .. } | Command::Reload => 0,
+    }
+}
+
+/**
+ * Bonus points for winning a game in the given time, the faster the better.
+ */
+pub fn get_time_bonus(elapsed: Duration) -> u32 {
+    if elapsed < MIN_BONUS_TIME {
+        return 0;
+    }
+
+    (700_000 / elapsed.as_secs()) as u32
+}
+
+/**
+ * Amount won or lost so far on a deal under Vegas scoring, having paid the ante.
+ */
+pub fn get_vegas_winnings(board: &Board) -> i64 {
+    let num_founded = board
+        .get_foundations()
+        .iter()
+        .map(|f| f.len())
+        .sum::<usize>();
+    num_founded as i64 * VEGAS_CARD_VALUE - VEGAS_ANTE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Move, Reveal, Rules};
+
+    fn transfer(source: Target, dest: Target) -> Command {
+        Command::Transfer {
+            source,
+            dest,
+            num_cards: 1,
+        }
+    }
+
+    #[test]
+    fn scores_commands() {
+        let cases = [
+            (
+                transfer(Target::Waste, Target::Foundation(0)),
+                DrawMode::One,
+                10,
+            ),
+            (
+                transfer(Target::Pile(2), Target::Foundation(1)),
+                DrawMode::One,
+                10,
+            ),
+            (transfer(Target::Waste, Target::Pile(3)), DrawMode::One, 5),
+            (transfer(Target::Pile(0), Target::Pile(3)), DrawMode::One, 0),
+            (
+                transfer(Target::Foundation(1), Target::Pile(4)),
+                DrawMode::One,
+                -15,
+            ),
+            (
+                transfer(Target::Foundation(1), Target::Foundation(2)),
+                DrawMode::One,
+                0,
+            ),
+            (
+                Command::Reveal {
+                    target: Target::Pile(5),
+                },
+                DrawMode::One,
+                5,
+            ),
+            (Command::Deal { num_cards: 3 }, DrawMode::Three, 0),
+            (Command::Reload, DrawMode::One, -100),
+            (Command::Reload, DrawMode::Three, 0),
+        ];
+
+        for (command, draw_mode, points) in cases {
+            assert_eq!(get_points(command, draw_mode), points, "{command:?}");
+        }
+    }
+
+    #[test]
+    fn adds_up_the_points_of_played_moves() {
+        let mut board = Board::from_seed(1);
+
+        let to_foundation = |source| Move::Transfer {
+            source,
+            dest: Target::Foundation(0),
+            num_cards: 1,
+        };
+
+        // The 7 of clubs goes onto the 8 of diamonds, then the ace and two of hearts
+        // to the foundations, leaving the 4 of hearts to turn over.
+        let moves = [
+            (Move::Deal, 0),
+            (Move::Deal, 0),
+            (
+                Move::Transfer {
+                    source: Target::Waste,
+                    dest: Target::Pile(2),
+                    num_cards: 1,
+                },
+                5,
+            ),
+            (Move::Deal, 5),
+            (Move::Deal, 5),
+            (Move::Deal, 5),
+            (to_foundation(Target::Waste), 15),
+            (to_foundation(Target::Pile(5)), 25),
+            (
+                Move::Reveal {
+                    target: Target::Pile(5),
+                },
+                30,
+            ),
+        ];
+
+        for (mv, score) in moves {
+            board.apply(mv).unwrap();
+            assert_eq!(get_score(&board), score, "{mv:?}");
+        }
+
+        board.undo().unwrap();
+
+        assert_eq!(get_score(&board), 25);
+    }
+
+    #[test]
+    fn scores_cards_turned_over_along_with_a_move_once() {
+        let mut board = Board::deal(1, Rules::default().reveal(Reveal::Auto));
+
+        // As above, but the 4 of hearts is turned over along with the 2 of hearts.
+        board.deal_from_stock().unwrap();
+        board.deal_from_stock().unwrap();
+        board
+            .apply(Move::Transfer {
+                source: Target::Waste,
+                dest: Target::Pile(2),
+                num_cards: 1,
+            })
+            .unwrap();
+        for _ in 0..3 {
+            board.deal_from_stock().unwrap();
+        }
+        for source in [Target::Waste, Target::Pile(5)] {
+            board
+                .apply(Move::Transfer {
+                    source,
+                    dest: Target::Foundation(0),
+                    num_cards: 1,
+                })
+                .unwrap();
+        }
+
+        assert_eq!(get_score(&board), 30);
+
+        board.undo().unwrap();
+
+        assert_eq!(get_score(&board), 15);
+        assert!(!board.get(Target::Pile(5)).unwrap()[4].is_visible());
+    }
+
+    #[test]
+    fn never_scores_below_zero() {
+        let mut board = Board::from_seed(1);
+
+        // Reloading costs 100 points, but there are none to lose yet.
+        while board.deal_from_stock().is_ok() {}
+        board.reload_stock().unwrap();
+
+        assert_eq!(get_score(&board), 0);
+
+        // The 7 of clubs goes from the waste onto the 8 of diamonds.
+        board.deal_from_stock().unwrap();
+        board.deal_from_stock().unwrap();
+        board
+            .apply(Move::Transfer {
+                source: Target::Waste,
+                dest: Target::Pile(2),
+                num_cards: 1,
+            })
+            .unwrap();
+
+        assert_eq!(get_score(&board), 5);
+    }
+
+    #[test]
+    fn only_gives_a_time_bonus_after_30_seconds() {
+        let cases = [(0, 0), (29, 0), (30, 23_333), (100, 7_000), (700_001, 0)];
+
+        for (seconds, bonus) in cases {
+            assert_eq!(get_time_bonus(Duration::from_secs(seconds)), bonus);
+        }
+    }
+}
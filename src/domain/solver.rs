@@ -0,0 +1,253 @@
+use std::{
+    cmp::Reverse,
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+use super::{get_priority, Board, Move, Rank, Target};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solution {
+    /**
+     * Moves which win the game when applied in order.
+     */
+    Solved(Vec<Move>),
+    /**
+     * No sequence of moves wins the game.
+     */
+    Unsolvable,
+    /**
+     * The search budget ran out, or the moves it left out might have won the game.
+     */
+    GaveUp,
+}
+
+/**
+ * Search for a winning sequence of moves, knowing where all face down cards are.
+ *
+ * The search is a depth-first search which never visits the same position twice.
+ * Moves unlikely to help, such as moving a run between piles without uncovering
+ * anything, are pruned. Since they might still be needed, e.g. to build another run
+ * on the card left behind, a search which pruned any ends with `GaveUp` rather than
+ * `Unsolvable` when it finds no solution.
+ */
+pub struct Solver {
+    max_nodes: usize,
+    time_limit: Option<Duration>,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self {
+            max_nodes: 1_000_000,
+            time_limit: None,
+        }
+    }
+}
+
+struct Frame {
+    moves: Vec<Move>,
+    next: usize,
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * Maximum number of moves to try before giving up.
+     */
+    pub fn max_nodes(mut self, value: usize) -> Self {
+        self.max_nodes = value;
+        self
+    }
+
+    pub fn time_limit(mut self, value: Duration) -> Self {
+        self.time_limit = Some(value);
+        self
+    }
+
+    pub fn solve(&self, board: &Board) -> Solution {
+        let started_at = Instant::now();
+
+        let mut board = board.clone();
+        let mut path = Vec::new();
+        let mut visited = HashSet::new();
+        let mut num_nodes = 0;
+        let mut pruned = false;
+
+        if board.is_won() {
+            return Solution::Solved(path);
+        }
+
+        visited.insert(board.get_position_key());
+
+        let mut stack = vec![Frame {
+            moves: get_candidate_moves(&board, &mut pruned),
+            next: 0,
+        }];
+
+        while let Some(frame) = stack.last_mut() {
+            if frame.next == frame.moves.len() {
+                // All moves were tried from this position: backtrack.
+                stack.pop();
+                if path.pop().is_some() {
+                    board.undo().unwrap();
+                }
+                continue;
+            }
+
+            if num_nodes >= self.max_nodes || self.is_out_of_time(started_at, num_nodes) {
+                return Solution::GaveUp;
+            }
+
+            let mv = frame.moves[frame.next];
+            frame.next += 1;
+
+            board.apply(mv).unwrap();
+            num_nodes += 1;
+
+            if board.is_won() {
+                path.push(mv);
+                return Solution::Solved(path);
+            }
+
+            if !visited.insert(board.get_position_key()) {
+                board.undo().unwrap();
+                continue;
+            }
+
+            path.push(mv);
+            stack.push(Frame {
+                moves: get_candidate_moves(&board, &mut pruned),
+                next: 0,
+            });
+        }
+
+        if pruned {
+            Solution::GaveUp
+        } else {
+            Solution::Unsolvable
+        }
+    }
+
+    fn is_out_of_time(&self, started_at: Instant, num_nodes: usize) -> bool {
+        // Reading the clock isn't free, so only do it every now and then.
+        match self.time_limit {
+            Some(limit) if num_nodes & 0x3ff == 0 => started_at.elapsed() >= limit,
+            _ => false,
+        }
+    }
+}
+
+/**
+ * Moves worth trying from a position, most promising first.
+ *
+ * Sets `pruned` when leaving out moves which might be needed to win.
+ */
+fn get_candidate_moves(board: &Board, pruned: &mut bool) -> Vec<Move> {
+    let mut moves = board.distinct_moves().collect::<Vec<_>>();
+
+    // Some moves can never hurt, so there is no need to try anything else.
+    if let Some(&mv) = moves
+        .iter()
+        .find(|&&mv| matches!(mv, Move::Reveal { .. }) || is_safe_to_found(board, mv))
+    {
+        return vec![mv];
+    }
+
+    // Moving a king between empty piles leads to an equivalent position.
+    moves.retain(|&mv| !is_king_shuffle(board, mv));
+
+    let num_moves = moves.len();
+    moves.retain(|&mv| is_useful(board, mv));
+    *pruned |= moves.len() < num_moves;
+
+    // Moves which can't make progress on their own come last.
+    moves.sort_by_key(|&mv| Reverse(get_priority(board, mv)));
+
+    moves
+}
+
+fn is_safe_to_found(board: &Board, mv: Move) -> bool {
+    match mv {
+        Move::Transfer {
+            source: source @ (Target::Waste | Target::Pile(_)),
+            dest: Target::Foundation(_),
+            ..
+        } => board.is_safe_to_found(source),
+        _ => false,
+    }
+}
+
+fn is_king_shuffle(board: &Board, mv: Move) -> bool {
+    match mv {
+        Move::Transfer {
+            source: source @ Target::Pile(_),
+            dest: Target::Pile(_),
+            ..
+        } => {
+            board.get_card_left_by(mv).is_none() && board.get(source).unwrap()[0].rank == Rank::KING
+        }
+        _ => false,
+    }
+}
+
+fn is_useful(board: &Board, mv: Move) -> bool {
+    match mv {
+        Move::Transfer {
+            source: Target::Pile(_),
+            dest: Target::Pile(_),
+            ..
+        } => match board.get_card_left_by(mv) {
+            // Uncovers a face down card.
+            Some(card) if !card.is_visible() => true,
+            // Uncovers a card which can then go to a foundation.
+            Some(card) => board.can_found(card),
+            // Empties a pile.
+            None => true,
+        },
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::GameOutcome;
+
+    const SOLVABLE_DEAL: u64 = 1;
+
+    #[test]
+    fn solves_a_seeded_deal() {
+        let board = Board::from_seed(SOLVABLE_DEAL);
+
+        let moves = match Solver::new().solve(&board) {
+            Solution::Solved(moves) => moves,
+            solution => panic!("deal {SOLVABLE_DEAL} wasn't solved: {solution:?}"),
+        };
+
+        let mut board = board;
+        for mv in moves {
+            board.apply(mv).unwrap();
+        }
+
+        assert_eq!(board.get_outcome(), GameOutcome::Won);
+    }
+
+    #[test]
+    fn finds_unsolvable_positions() {
+        // The queen of clubs covers the jack, and no red king is left to move it onto.
+        let board = "
+            stock: -
+            waste: -
+            foundations: AS 2S 3S 4S 5S 6S 7S 8S 9S 10S JS QS KS | AH 2H 3H 4H 5H 6H 7H 8H 9H 10H JH QH | AD 2D 3D 4D 5D 6D 7D 8D 9D 10D JD QD | AC 2C 3C 4C 5C 6C 7C 8C 9C 10C
+            tableau: KH KD KC JC QC | - | - | - | - | - | -
+        "
+        .parse::<Board>()
+        .unwrap();
+
+        assert_eq!(Solver::new().solve(&board), Solution::Unsolvable);
+    }
+}
@@ -0,0 +1,50 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use super::get_data_dir;
+
+/**
+ * Money won or lost over all Vegas games played with cumulative scoring, kept on disk.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct Bankroll {
+    balance: i64,
+    path: Option<PathBuf>,
+}
+
+impl Bankroll {
+    /**
+     * Read the bankroll from the data directory, starting from zero if there is none yet.
+     */
+    pub fn load() -> Self {
+        let path = get_data_dir().map(|dir| dir.join("bankroll.txt"));
+
+        let balance = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|text| text.trim().parse().ok())
+            .unwrap_or(0);
+
+        Self { balance, path }
+    }
+
+    pub fn get_balance(&self) -> i64 {
+        self.balance
+    }
+
+    /**
+     * Add the winnings of a game, which may be negative, and write the new balance to disk.
+     */
+    pub fn add(&mut self, amount: i64) -> Result<(), Box<dyn Error>> {
+        self.balance += amount;
+
+        if let Some(path) = &self.path {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+
+            fs::write(path, format!("{}\n", self.balance))?;
+        }
+
+        Ok(())
+    }
+}
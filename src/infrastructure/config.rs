@@ -0,0 +1,270 @@
+use std::{error::Error, fmt, path::PathBuf};
+
+use crate::domain::{DrawMode, Reveal, Rules, Variant};
+
+pub const USAGE: &str = "\
+Play Solitaire in the terminal.
+
+Usage: tui [OPTIONS]
+
+Options:
+  --seed <NUMBER>      Play the deal with the given number
+  --draw <1|3>         Number of cards dealt from the stock [default: 1]
+  --variant <NAME>     Rules to play with: klondike, vegas [default: klondike]
+  --passes <N>         Passes allowed through the stock, or 'unlimited'
+                       [default: unlimited for klondike, 1 or 3 for vegas]
+  --auto-reveal        Turn over cards as soon as they are exposed
+  --cumulative         Carry the vegas balance over from game to game
+  --auto-complete      Finish the game automatically once all cards are face up
+  --autoplay           Move cards to the foundations whenever it is safe
+  --load <FILE>        Resume a saved game
+  --import <FILE>      Replay a game record, then carry on playing
+  --replay <FILE>      Watch a game record move by move
+  --theme <NAME>       Colors to use: classic, dark, mono [default: classic]
+  --ascii              Only draw ASCII characters
+  -h, --help           Print help
+  -V, --version        Print version";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeName {
+    #[default]
+    Classic,
+    Dark,
+    Mono,
+}
+
+/**
+ * Options to start the game with.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub seed: Option<u64>,
+    pub rules: Rules,
+    /**
+     * Whether the Vegas balance carries over from game to game.
+     */
+    pub cumulative: bool,
+    /**
+     * Whether the game finishes by itself once all cards are face up.
+     */
+    pub auto_complete: bool,
+    /**
+     * Whether cards go to the foundations by themselves after each move, when it is safe.
+     */
+    pub autoplay: bool,
+    pub load: Option<PathBuf>,
+    pub import: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub theme: ThemeName,
+    pub ascii: bool,
+}
+
+#[derive(Debug)]
+pub enum Invocation {
+    Run(Config),
+    Help,
+    Version,
+}
+
+#[derive(Debug)]
+pub struct ArgsError(String);
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for ArgsError {}
+
+/**
+ * Parse command line arguments, excluding the program name.
+ */
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Invocation, ArgsError> {
+    let mut config = Config::default();
+    let mut variant = Variant::default();
+    let mut draw_mode = DrawMode::default();
+    let mut passes = None;
+    let mut reveal = Reveal::default();
+    // Options choosing the deal or its rules, which saved games and game records come with.
+    let mut deal_options = Vec::new();
+
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let mut value_of = |name: &str| {
+            args.next()
+                .ok_or_else(|| ArgsError(format!("{name} expects a value")))
+        };
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Invocation::Help),
+            "-V" | "--version" => return Ok(Invocation::Version),
+            "--seed" => {
+                deal_options.push("--seed");
+                let value = value_of("--seed")?;
+                let seed = value
+                    .parse()
+                    .map_err(|_| ArgsError(format!("invalid deal number: {value}")))?;
+                config.seed = Some(seed);
+            }
+            "--draw" => {
+                deal_options.push("--draw");
+                let value = value_of("--draw")?;
+                draw_mode = value
+                    .parse()
+                    .map_err(|_| ArgsError(format!("invalid draw count: {value}")))?;
+            }
+            "--variant" => {
+                deal_options.push("--variant");
+                let value = value_of("--variant")?;
+                variant = value
+                    .parse()
+                    .map_err(|_| ArgsError(format!("unknown variant: {value}")))?;
+            }
+            "--passes" => {
+                deal_options.push("--passes");
+                let value = value_of("--passes")?;
+                let count = value
+                    .parse()
+                    .map_err(|_| ArgsError(format!("invalid number of passes: {value}")))?;
+                passes = Some(count);
+            }
+            "--load" => config.load = Some(PathBuf::from(value_of("--load")?)),
+            "--import" => config.import = Some(PathBuf::from(value_of("--import")?)),
+            "--replay" => config.replay = Some(PathBuf::from(value_of("--replay")?)),
+            "--theme" => {
+                config.theme = match value_of("--theme")?.as_str() {
+                    "classic" => ThemeName::Classic,
+                    "dark" => ThemeName::Dark,
+                    "mono" => ThemeName::Mono,
+                    value => return Err(ArgsError(format!("unknown theme: {value}"))),
+                };
+            }
+            "--auto-reveal" => {
+                deal_options.push("--auto-reveal");
+                reveal = Reveal::Auto;
+            }
+            "--cumulative" => config.cumulative = true,
+            "--auto-complete" => config.auto_complete = true,
+            "--autoplay" => config.autoplay = true,
+            "--ascii" => config.ascii = true,
+            _ => return Err(ArgsError(format!("unexpected argument: {arg}"))),
+        }
+    }
+
+    let games = [
+        ("--load", &config.load),
+        ("--import", &config.import),
+        ("--replay", &config.replay),
+    ]
+    .into_iter()
+    .filter(|(_, path)| path.is_some())
+    .map(|(name, _)| name)
+    .collect::<Vec<_>>();
+
+    if let [first, second, ..] = games[..] {
+        return Err(ArgsError(format!("{first} can't be used with {second}")));
+    }
+
+    if let (Some(game), Some(option)) = (games.first(), deal_options.first()) {
+        return Err(ArgsError(format!("{game} can't be used with {option}")));
+    }
+
+    config.rules = Rules::for_variant(variant, draw_mode).reveal(reveal);
+
+    if let Some(passes) = passes {
+        config.rules = config.rules.passes(passes);
+    }
+
+    Ok(Invocation::Run(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Passes;
+
+    fn parse(args: &str) -> Result<Invocation, ArgsError> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    fn parse_config(args: &str) -> Config {
+        match parse(args) {
+            Ok(Invocation::Run(config)) => config,
+            result => panic!("'{args}' didn't parse to a config: {result:?}"),
+        }
+    }
+
+    fn parse_error(args: &str) -> String {
+        match parse(args) {
+            Err(err) => err.to_string(),
+            result => panic!("'{args}' was accepted: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn reads_options() {
+        let config = parse_config("--seed 12 --variant vegas --draw 3 --auto-reveal --theme dark");
+
+        assert_eq!(config.seed, Some(12));
+        assert_eq!(
+            config.rules,
+            Rules::for_variant(Variant::Vegas, DrawMode::Three).reveal(Reveal::Auto)
+        );
+        assert_eq!(config.theme, ThemeName::Dark);
+
+        let config = parse_config("--passes 2 --ascii");
+
+        assert_eq!(config.rules.passes, Passes::Limited(2));
+        assert!(config.ascii);
+
+        let config = parse_config("--load game.txt --autoplay");
+
+        assert_eq!(config.load, Some(PathBuf::from("game.txt")));
+        assert!(config.autoplay);
+
+        assert!(matches!(parse("--draw 3 --help"), Ok(Invocation::Help)));
+        assert!(matches!(parse("-V"), Ok(Invocation::Version)));
+    }
+
+    #[test]
+    fn rejects_missing_values() {
+        assert_eq!(parse_error("--seed"), "--seed expects a value");
+        assert_eq!(parse_error("--draw 3 --load"), "--load expects a value");
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        assert_eq!(parse_error("--passes 0"), "invalid number of passes: 0");
+        assert_eq!(parse_error("--draw 2"), "invalid draw count: 2");
+        assert_eq!(parse_error("--seed -1"), "invalid deal number: -1");
+        assert_eq!(parse_error("--theme pink"), "unknown theme: pink");
+    }
+
+    #[test]
+    fn rejects_unknown_arguments() {
+        assert_eq!(parse_error("--undo"), "unexpected argument: --undo");
+        assert_eq!(parse_error("3"), "unexpected argument: 3");
+    }
+
+    #[test]
+    fn rejects_conflicting_options() {
+        assert_eq!(
+            parse_error("--load game.txt --seed 12"),
+            "--load can't be used with --seed"
+        );
+        assert_eq!(
+            parse_error("--draw 3 --load game.txt"),
+            "--load can't be used with --draw"
+        );
+        assert_eq!(
+            parse_error("--import game.txt --auto-reveal"),
+            "--import can't be used with --auto-reveal"
+        );
+        assert_eq!(
+            parse_error("--replay game.txt --import game.txt"),
+            "--import can't be used with --replay"
+        );
+    }
+}
@@ -1,22 +1,103 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::Duration,
+};
 
-use crate::domain::{Board, StateMachine};
+use crate::domain::{
+    export_game, get_vegas_winnings, import_game, Board, GameOutcome, StateMachine, Target, Variant,
+};
+
+use super::{get_autosave_path, get_records_dir, Bankroll, Config, SavedGame, Stats};
 
 pub struct Container {
+    config: Config,
     board: Rc<RefCell<Board>>,
     state_machine: Rc<RefCell<StateMachine>>,
+    bankroll: RefCell<Bankroll>,
+    stats: Rc<RefCell<Stats>>,
+    /**
+     * Unfinished game saved when last quitting, which the game asked for on the
+     * command line is about to replace.
+     */
+    replaced_game: RefCell<Option<SavedGame>>,
+    /**
+     * Whether the current game was already counted, e.g. as soon as it was won.
+     */
+    settled: Cell<bool>,
 }
 
 impl Default for Container {
     fn default() -> Self {
-        Self {
-            board: Rc::new(RefCell::new(Board::default())),
-            state_machine: Rc::new(RefCell::new(StateMachine::default())),
-        }
+        Self::with_board(Config::default(), Board::default())
     }
 }
 
 impl Container {
+    pub fn new(config: Config) -> Result<Self, Box<dyn Error>> {
+        if let Some(path) = &config.load {
+            let saved = SavedGame::read(path)
+                .map_err(|err| format!("can't load {}: {err}", path.display()))?;
+
+            let container = Self::with_board(config, Board::default());
+            container.resume(saved);
+            return Ok(container);
+        }
+
+        if let Some(path) = config.import.as_ref().or(config.replay.as_ref()) {
+            let board = fs::read_to_string(path)
+                .map_err(Box::<dyn Error>::from)
+                .and_then(|text| Ok(import_game(&text)?))
+                .map_err(|err| format!("can't import {}: {err}", path.display()))?;
+
+            return Ok(Self::with_board(config, board));
+        }
+
+        let board = match config.seed {
+            Some(seed) => Board::deal(seed, config.rules),
+            None => Board::new(config.rules),
+        };
+
+        Ok(Self::with_board(config, board))
+    }
+
+    fn with_board(config: Config, board: Board) -> Self {
+        let bankroll = if config.cumulative {
+            Bankroll::load()
+        } else {
+            Bankroll::default()
+        };
+
+        let state_machine = StateMachine::default().autoplay(config.autoplay);
+
+        // Replays are only watched, so they never replace the saved game.
+        let replaces_autosave =
+            asks_for_a_game(&config) && config.replay.is_none() && !loads_autosave(&config);
+
+        let replaced_game = if replaces_autosave {
+            read_unfinished_game()
+        } else {
+            None
+        };
+
+        Self {
+            config,
+            board: Rc::new(RefCell::new(board)),
+            state_machine: Rc::new(RefCell::new(state_machine)),
+            bankroll: RefCell::new(bankroll),
+            stats: Rc::new(RefCell::new(Stats::load())),
+            replaced_game: RefCell::new(replaced_game),
+            settled: Cell::new(false),
+        }
+    }
+
+    pub fn get_config(&self) -> &Config {
+        &self.config
+    }
+
     pub fn get_board(&self) -> Rc<RefCell<Board>> {
         Rc::clone(&self.board)
     }
@@ -24,4 +105,214 @@ impl Container {
     pub fn get_state_machine(&self) -> Rc<RefCell<StateMachine>> {
         Rc::clone(&self.state_machine)
     }
+
+    pub fn get_stats(&self) -> Rc<RefCell<Stats>> {
+        Rc::clone(&self.stats)
+    }
+
+    /**
+     * Money won or lost under Vegas scoring, including previous games when cumulative.
+     */
+    pub fn get_balance(&self) -> i64 {
+        self.bankroll.borrow().get_balance() + get_vegas_winnings(&self.board.borrow())
+    }
+
+    /**
+     * Count the game in the statistics and add its winnings to the bankroll, once it is over.
+     * Games are only counted once.
+     */
+    fn settle(&self) -> Result<(), Box<dyn Error>> {
+        if self.settled.replace(true) {
+            return Ok(());
+        }
+
+        let state_machine = self.state_machine.borrow();
+
+        self.settle_game(
+            &self.board.borrow(),
+            state_machine.get_num_moves(),
+            state_machine.get_elapsed(),
+        )
+    }
+
+    fn settle_game(
+        &self,
+        board: &Board,
+        num_moves: u32,
+        elapsed: Duration,
+    ) -> Result<(), Box<dyn Error>> {
+        // Games given up on before the first move don't count as played.
+        if num_moves > 0 {
+            self.stats.borrow_mut().record(board, num_moves, elapsed)?;
+        }
+
+        if !self.config.cumulative || board.get_rules().variant != Variant::Vegas {
+            return Ok(());
+        }
+
+        self.bankroll.borrow_mut().add(get_vegas_winnings(board))
+    }
+
+    /**
+     * Start a new deal with the same rules.
+     */
+    pub fn new_game(&self) -> Result<(), Box<dyn Error>> {
+        self.settle()?;
+
+        let rules = *self.board.borrow().get_rules();
+        *self.board.borrow_mut() = Board::new(rules);
+        *self.state_machine.borrow_mut() = StateMachine::default().autoplay(self.config.autoplay);
+        self.settled.set(false);
+
+        Ok(())
+    }
+
+    /**
+     * Start the same deal over, the attempt so far counting as a game of its own.
+     */
+    pub fn restart(&self) -> Result<(), Box<dyn Error>> {
+        self.settle()?;
+
+        let board = self.board.borrow().get_initial_position();
+        *self.board.borrow_mut() = board;
+        *self.state_machine.borrow_mut() = StateMachine::default().autoplay(self.config.autoplay);
+        self.settled.set(false);
+
+        Ok(())
+    }
+
+    /**
+     * Continue a saved game where it was left off.
+     */
+    pub fn resume(&self, saved: SavedGame) {
+        *self.board.borrow_mut() = saved.board;
+        *self.state_machine.borrow_mut() =
+            StateMachine::restore(saved.cursor, saved.num_moves, saved.elapsed)
+                .autoplay(self.config.autoplay);
+        self.settled.set(false);
+    }
+
+    /**
+     * Give up on a saved game rather than resuming it.
+     */
+    pub fn abandon(&self, saved: SavedGame) -> Result<(), Box<dyn Error>> {
+        self.settle_game(&saved.board, saved.num_moves, saved.elapsed)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let state_machine = self.state_machine.borrow();
+
+        let saved = SavedGame {
+            board: self.board.borrow().clone(),
+            cursor: state_machine.get_cursor().unwrap_or(Target::Stock),
+            num_moves: state_machine.get_num_moves(),
+            elapsed: state_machine.get_elapsed(),
+        };
+
+        saved.write(path)
+    }
+
+    /**
+     * Count the game and forget about its save as soon as it is won, rather than
+     * when leaving it, so that the win isn't lost if the game doesn't quit normally.
+     */
+    pub fn finish_if_won(&self) -> Result<(), Box<dyn Error>> {
+        if self.state_machine.borrow().get_outcome() != GameOutcome::Won {
+            return Ok(());
+        }
+
+        self.autosave()
+    }
+
+    /**
+     * Save the game so that it can be resumed later, or forget about it once won
+     * or if it was never started.
+     */
+    pub fn autosave(&self) -> Result<(), Box<dyn Error>> {
+        let is_won = self.board.borrow().get_outcome() == GameOutcome::Won;
+        let is_started = self.state_machine.borrow().get_num_moves() > 0;
+
+        if is_won {
+            self.settle()?;
+        }
+
+        let path = match get_autosave_path() {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        // Nothing was played instead, so the game saved before can still be resumed.
+        if !is_started && self.replaced_game.borrow().is_some() {
+            return Ok(());
+        }
+
+        // The game saved before is about to be lost, so it counts as given up on.
+        if let Some(saved) = self.replaced_game.take() {
+            self.abandon(saved)?;
+        }
+
+        if is_started && !is_won {
+            return self.save(&path);
+        }
+
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+
+        Ok(())
+    }
+
+    /**
+     * The game saved when last quitting, if it is unfinished and no particular game was asked for.
+     */
+    pub fn find_unfinished_game(&self) -> Option<SavedGame> {
+        if asks_for_a_game(&self.config) {
+            return None;
+        }
+
+        read_unfinished_game()
+    }
+
+    /**
+     * Write down the game as a game record, returning the path of the file.
+     */
+    pub fn export(&self) -> Result<PathBuf, Box<dyn Error>> {
+        let dir = get_records_dir().ok_or("there is no directory to export games to")?;
+        let board = self.board.borrow();
+
+        let name = match board.get_deal_number() {
+            Some(deal_number) => format!("deal-{deal_number}.txt"),
+            None => "position.txt".to_string(),
+        };
+
+        fs::create_dir_all(&dir)?;
+
+        let path = dir.join(name);
+        fs::write(&path, export_game(&board))?;
+
+        Ok(path)
+    }
+}
+
+fn asks_for_a_game(config: &Config) -> bool {
+    config.seed.is_some()
+        || config.load.is_some()
+        || config.import.is_some()
+        || config.replay.is_some()
+}
+
+fn loads_autosave(config: &Config) -> bool {
+    let canonicalize = |path: &Path| fs::canonicalize(path).ok();
+
+    match (&config.load, get_autosave_path()) {
+        (Some(load), Some(autosave)) => canonicalize(load) == canonicalize(&autosave),
+        _ => false,
+    }
+}
+
+fn read_unfinished_game() -> Option<SavedGame> {
+    // A save file which can't be read is as good as no save file.
+    let saved = SavedGame::read(&get_autosave_path()?).ok()?;
+
+    (saved.board.get_outcome() != GameOutcome::Won).then_some(saved)
 }
@@ -1,3 +1,13 @@
+mod bankroll;
+mod config;
 mod container;
+mod paths;
+mod save;
+mod stats;
 
+pub use bankroll::*;
+pub use config::*;
 pub use container::*;
+pub use paths::*;
+pub use save::*;
+pub use stats::*;
@@ -0,0 +1,27 @@
+use std::{env, path::PathBuf};
+
+/**
+ * Directory where games and other data are stored, following the XDG base directory spec.
+ */
+pub fn get_data_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".local/share"),
+    };
+
+    Some(base.join("solitaire-rs"))
+}
+
+/**
+ * File where the game in progress is saved when quitting.
+ */
+pub fn get_autosave_path() -> Option<PathBuf> {
+    Some(get_data_dir()?.join("autosave.txt"))
+}
+
+/**
+ * Directory where games are exported as game records.
+ */
+pub fn get_records_dir() -> Option<PathBuf> {
+    Some(get_data_dir()?.join("games"))
+}
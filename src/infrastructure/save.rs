@@ -0,0 +1,455 @@
+use std::{error::Error, fmt, fs, path::Path, str::FromStr, time::Duration};
+
+use crate::domain::{
+    format_pile, parse_pile, Board, Command, DrawMode, History, Passes, Pile, Reveal, Rules,
+    Target, Variant,
+};
+
+const HEADER: &str = "solitaire-rs save 2";
+
+/**
+ * Everything needed to resume a game where it was left off.
+ *
+ * Games are saved as text, one field per line:
+ *
+ * ```text
+ * solitaire-rs save 2
+ * deal 42
+ * variant klondike
+ * draw 1
+ * passes unlimited
+ * reveal manual
+ * pass 1
+ * stock ?4H ?KS
+ * waste 5D
+ * foundation AH 2H
+ * foundation -
+ * pile ?3C 10D
+ * done 3>F1x1 deal:1 reveal:3
+ * undone
+ * cursor 3
+ * moves 3
+ * elapsed 754
+ * ```
+ *
+ * Piles are written in the same notation as positions, listing their cards
+ * from bottom to top, e.g. `?QH` for a face down queen of hearts, or `-` when
+ * empty. They are referred to as in game records, e.g. `3` or `F1`. There are
+ * four `foundation` and seven `pile` lines. Moves are separated by spaces, and
+ * the commands making up a single move by `+`. The deal number is left out for
+ * games which didn't start from a numbered deal.
+ */
+#[derive(Debug, Clone)]
+pub struct SavedGame {
+    pub board: Board,
+    pub cursor: Target,
+    pub num_moves: u32,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct LoadError {
+    line: Option<usize>,
+    reason: String,
+}
+
+impl LoadError {
+    fn new(line: Option<usize>, reason: impl Into<String>) -> Self {
+        Self {
+            line,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.reason),
+            None => write!(f, "{}", self.reason),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+impl SavedGame {
+    pub fn read(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let text = fs::read_to_string(path)?;
+        Ok(text.parse()?)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for SavedGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let board = &self.board;
+        let rules = board.get_rules();
+
+        writeln!(f, "{HEADER}")?;
+        if let Some(deal_number) = board.get_deal_number() {
+            write_field(f, "deal", deal_number)?;
+        }
+
+        write_field(f, "variant", rules.variant)?;
+        write_field(f, "draw", rules.draw_mode)?;
+        write_field(f, "passes", rules.passes)?;
+        write_field(f, "reveal", rules.reveal)?;
+        write_field(f, "pass", board.get_pass())?;
+        write_field(f, "stock", format_pile(board.get_stock()))?;
+        write_field(f, "waste", format_pile(board.get_waste()))?;
+
+        for foundation in board.get_foundations() {
+            write_field(f, "foundation", format_pile(foundation))?;
+        }
+
+        for pile in board.get_tableau() {
+            write_field(f, "pile", format_pile(pile))?;
+        }
+
+        let history = board.get_history();
+        write_field(f, "done", format_moves(history.iter()))?;
+        write_field(f, "undone", format_moves(history.iter_undone()))?;
+        write_field(f, "cursor", self.cursor)?;
+        write_field(f, "moves", self.num_moves)?;
+        write_field(f, "elapsed", self.elapsed.as_secs())
+    }
+}
+
+fn write_field(f: &mut fmt::Formatter<'_>, key: &str, value: impl fmt::Display) -> fmt::Result {
+    let value = value.to_string();
+
+    if value.is_empty() {
+        writeln!(f, "{key}")
+    } else {
+        writeln!(f, "{key} {value}")
+    }
+}
+
+#[derive(Default)]
+struct Fields {
+    deal: Option<u64>,
+    variant: Option<Variant>,
+    draw_mode: Option<DrawMode>,
+    passes: Option<Passes>,
+    reveal: Option<Reveal>,
+    pass: Option<u32>,
+    stock: Option<Pile>,
+    waste: Option<Pile>,
+    foundations: Vec<Pile>,
+    tableau: Vec<Pile>,
+    done: Option<Vec<Vec<Command>>>,
+    undone: Option<Vec<Vec<Command>>>,
+    cursor: Option<Target>,
+    num_moves: Option<u32>,
+    elapsed: Option<Duration>,
+}
+
+impl FromStr for SavedGame {
+    type Err = LoadError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        if lines.next().map(|(_, line)| line) != Some(HEADER) {
+            return Err(LoadError::new(Some(1), "not a saved game"));
+        }
+
+        let mut fields = Fields::default();
+
+        for (number, line) in lines {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            let value = value.trim();
+
+            let result = match key {
+                "deal" => set(&mut fields.deal, parse_number(value)),
+                "variant" => set(&mut fields.variant, parse_value(value)),
+                "draw" => set(&mut fields.draw_mode, parse_value(value)),
+                "passes" => set(&mut fields.passes, parse_value(value)),
+                "reveal" => set(&mut fields.reveal, parse_value(value)),
+                "pass" => set(&mut fields.pass, parse_number(value)),
+                "stock" => set(&mut fields.stock, read_pile(value)),
+                "waste" => set(&mut fields.waste, read_pile(value)),
+                "foundation" => read_pile(value).map(|pile| fields.foundations.push(pile)),
+                "pile" => read_pile(value).map(|pile| fields.tableau.push(pile)),
+                "done" => set(&mut fields.done, parse_moves(value)),
+                "undone" => set(&mut fields.undone, parse_moves(value)),
+                "cursor" => set(&mut fields.cursor, parse_value(value)),
+                "moves" => set(&mut fields.num_moves, parse_number(value)),
+                "elapsed" => set(
+                    &mut fields.elapsed,
+                    parse_number(value).map(Duration::from_secs),
+                ),
+                _ => Err(format!("unknown field '{key}'")),
+            };
+
+            result.map_err(|reason| LoadError::new(Some(number), reason))?;
+        }
+
+        fields
+            .into_game()
+            .map_err(|reason| LoadError::new(None, reason))
+    }
+}
+
+impl Fields {
+    fn into_game(self) -> Result<SavedGame, String> {
+        let rules = Rules {
+            variant: required(self.variant, "variant")?,
+            draw_mode: required(self.draw_mode, "draw")?,
+            passes: required(self.passes, "passes")?,
+            reveal: self.reveal.unwrap_or_default(),
+        };
+
+        let mut board = Board::from_piles(
+            self.deal,
+            rules,
+            self.tableau,
+            self.foundations,
+            required(self.stock, "stock")?,
+            required(self.waste, "waste")?,
+        )
+        .map_err(|err| err.to_string())?;
+
+        let history = History::from_moves(
+            self.done.unwrap_or_default(),
+            self.undone.unwrap_or_default(),
+        );
+
+        board
+            .restore_history(required(self.pass, "pass")?, history)
+            .map_err(|err| err.to_string())?;
+
+        let cursor = self.cursor.unwrap_or(Target::Stock);
+
+        if board.get(cursor).is_none() {
+            return Err("the cursor is on a pile which doesn't exist".into());
+        }
+
+        Ok(SavedGame {
+            board,
+            cursor,
+            num_moves: self.num_moves.unwrap_or_default(),
+            elapsed: self.elapsed.unwrap_or_default(),
+        })
+    }
+}
+
+fn set<T>(field: &mut Option<T>, value: Result<T, String>) -> Result<(), String> {
+    if field.is_some() {
+        return Err("duplicate field".into());
+    }
+
+    *field = Some(value?);
+    Ok(())
+}
+
+fn required<T>(field: Option<T>, name: &str) -> Result<T, String> {
+    field.ok_or_else(|| format!("missing field '{name}'"))
+}
+
+fn parse_number<T: FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid number '{value}'"))
+}
+
+fn parse_value<T>(value: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    value.parse().map_err(|err: T::Err| err.to_string())
+}
+
+fn read_pile(value: &str) -> Result<Pile, String> {
+    parse_pile(value).map_err(|err| err.to_string())
+}
+
+fn format_moves<'a>(moves: impl Iterator<Item = &'a Vec<Command>>) -> String {
+    moves
+        .map(|commands| {
+            commands
+                .iter()
+                .map(|&command| format_command(command))
+                .collect::<Vec<_>>()
+                .join("+")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_moves(value: &str) -> Result<Vec<Vec<Command>>, String> {
+    value
+        .split_whitespace()
+        .map(|commands| commands.split('+').map(parse_command).collect())
+        .collect()
+}
+
+fn format_command(command: Command) -> String {
+    match command {
+        Command::Transfer {
+            source,
+            dest,
+            num_cards,
+        } => format!("{source}>{dest}x{num_cards}"),
+        Command::Deal { num_cards } => format!("deal:{num_cards}"),
+        Command::Reload => "reload".into(),
+        Command::Reveal { target } => format!("reveal:{target}"),
+    }
+}
+
+fn parse_command(value: &str) -> Result<Command, String> {
+    if value == "reload" {
+        return Ok(Command::Reload);
+    }
+
+    if let Some(num_cards) = value.strip_prefix("deal:") {
+        return Ok(Command::Deal {
+            num_cards: parse_number(num_cards)?,
+        });
+    }
+
+    if let Some(target) = value.strip_prefix("reveal:") {
+        return Ok(Command::Reveal {
+            target: parse_value(target)?,
+        });
+    }
+
+    let invalid = || format!("invalid move '{value}'");
+
+    let (source, rest) = value.split_once('>').ok_or_else(invalid)?;
+    let (dest, num_cards) = rest.split_once('x').ok_or_else(invalid)?;
+
+    Ok(Command::Transfer {
+        source: parse_value(source)?,
+        dest: parse_value(dest)?,
+        num_cards: parse_number(num_cards)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_saved_game() -> SavedGame {
+        let mut board = Board::deal(3, Rules::for_variant(Variant::Vegas, DrawMode::Three));
+
+        board.deal_from_stock().unwrap();
+
+        for _ in 0..7 {
+            let mv = board.legal_moves().last().unwrap();
+            board.apply(mv).unwrap();
+        }
+        board.undo().unwrap();
+
+        SavedGame {
+            board,
+            cursor: Target::Pile(3),
+            num_moves: 9,
+            elapsed: Duration::from_secs(754),
+        }
+    }
+
+    /**
+     * Write the game out, replacing the line of the given field.
+     */
+    fn write_with(saved: &SavedGame, key: &str, value: &str) -> String {
+        saved
+            .to_string()
+            .lines()
+            .map(|line| match line.split_once(' ').unwrap_or((line, "")) {
+                (other, _) if other == key => format!("{key} {value}"),
+                _ => line.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn reads_back_saved_games() {
+        let saved = get_saved_game();
+        let text = saved.to_string();
+
+        let restored = text.parse::<SavedGame>().unwrap();
+
+        assert_eq!(restored.to_string(), text);
+        assert_eq!(restored.board.to_string(), saved.board.to_string());
+        assert_eq!(restored.board.get_pass(), saved.board.get_pass());
+        assert_eq!(restored.cursor, saved.cursor);
+        assert_eq!(restored.num_moves, saved.num_moves);
+        assert_eq!(restored.elapsed, saved.elapsed);
+        assert_eq!(
+            restored.board.get_initial_position().to_string(),
+            Board::deal(3, Rules::default()).to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_moves_not_matching_the_position() {
+        let saved = get_saved_game();
+
+        // The stock was never reloaded.
+        let text = write_with(&saved, "done", "deal:3 reload");
+
+        assert_eq!(
+            text.parse::<SavedGame>().unwrap_err().to_string(),
+            "Moves don't match the position"
+        );
+
+        // There is no eighth pile.
+        let text = write_with(&saved, "undone", "8>F1x1");
+
+        assert_eq!(
+            text.parse::<SavedGame>().unwrap_err().to_string(),
+            "Moves don't match the position"
+        );
+    }
+
+    #[test]
+    fn rejects_passes_out_of_range() {
+        let saved = get_saved_game();
+
+        for pass in ["0", "4"] {
+            assert_eq!(
+                write_with(&saved, "pass", pass)
+                    .parse::<SavedGame>()
+                    .unwrap_err()
+                    .to_string(),
+                "The pass through the stock is out of range"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_cursors_on_missing_piles() {
+        let saved = get_saved_game();
+
+        assert_eq!(
+            write_with(&saved, "cursor", "F5")
+                .parse::<SavedGame>()
+                .unwrap_err()
+                .to_string(),
+            "the cursor is on a pile which doesn't exist"
+        );
+        assert_eq!(
+            write_with(&saved, "cursor", "P3")
+                .parse::<SavedGame>()
+                .unwrap_err()
+                .to_string(),
+            "line 23: 'P3' is not a pile"
+        );
+    }
+}
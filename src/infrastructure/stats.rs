@@ -0,0 +1,297 @@
+use std::{error::Error, fmt, fs, path::PathBuf, time::Duration};
+
+use crate::domain::{
+    get_score, get_time_bonus, get_vegas_winnings, Board, DrawMode, GameOutcome, Variant,
+};
+
+use super::get_data_dir;
+
+/**
+ * How games played with the same variant and draw mode went.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GameStats {
+    pub played: u32,
+    pub won: u32,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub fastest_win: Option<Duration>,
+    pub fewest_moves: Option<u32>,
+    /**
+     * Highest score, or highest winnings for Vegas games, won or not.
+     */
+    pub best_score: Option<i64>,
+}
+
+impl GameStats {
+    /**
+     * Percentage of games won, if any were played.
+     */
+    pub fn get_win_rate(&self) -> Option<u32> {
+        (self.won * 100).checked_div(self.played)
+    }
+
+    fn add(&mut self, won: bool, num_moves: u32, elapsed: Duration, score: i64) {
+        self.played += 1;
+        self.best_score = Some(self.best_score.map_or(score, |best| best.max(score)));
+
+        if !won {
+            self.current_streak = 0;
+            return;
+        }
+
+        self.won += 1;
+        self.current_streak += 1;
+        self.best_streak = self.best_streak.max(self.current_streak);
+        self.fastest_win = Some(self.fastest_win.map_or(elapsed, |best| best.min(elapsed)));
+        self.fewest_moves = Some(
+            self.fewest_moves
+                .map_or(num_moves, |best| best.min(num_moves)),
+        );
+    }
+}
+
+/**
+ * Statistics over all games played, broken down by variant and draw mode, kept on disk.
+ *
+ * They are saved as text, with a section for each variant and draw mode played:
+ *
+ * ```text
+ * [klondike draw 1]
+ * played 12
+ * won 4
+ * current-streak 2
+ * best-streak 3
+ * fastest-win 312
+ * fewest-moves 98
+ * best-score 4521
+ * ```
+ */
+#[derive(Debug, Clone, Default)]
+pub struct Stats {
+    entries: Vec<(Variant, DrawMode, GameStats)>,
+    path: Option<PathBuf>,
+}
+
+impl Stats {
+    /**
+     * Read the statistics from the data directory, starting afresh if there are none yet.
+     */
+    pub fn load() -> Self {
+        let path = get_data_dir().map(|dir| dir.join("stats.txt"));
+
+        let entries = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|text| parse_entries(&text))
+            .unwrap_or_default();
+
+        Self { entries, path }
+    }
+
+    pub fn get(&self, variant: Variant, draw_mode: DrawMode) -> GameStats {
+        self.iter()
+            .find(|(v, d, _)| *v == variant && *d == draw_mode)
+            .map(|&(_, _, stats)| stats)
+            .unwrap_or_default()
+    }
+
+    /**
+     * Iterate over the variants and draw modes played so far.
+     */
+    pub fn iter(&self) -> impl Iterator<Item = &(Variant, DrawMode, GameStats)> {
+        self.entries.iter()
+    }
+
+    /**
+     * Count a game which is over, whether won or given up on, and write the new statistics to disk.
+     */
+    pub fn record(
+        &mut self,
+        board: &Board,
+        num_moves: u32,
+        elapsed: Duration,
+    ) -> Result<(), Box<dyn Error>> {
+        let rules = board.get_rules();
+        let won = board.get_outcome() == GameOutcome::Won;
+
+        let score = match rules.variant {
+            Variant::Vegas => get_vegas_winnings(board),
+            Variant::Klondike if won => (get_score(board) + get_time_bonus(elapsed)).into(),
+            Variant::Klondike => get_score(board).into(),
+        };
+
+        let index = match self
+            .entries
+            .iter()
+            .position(|(v, d, _)| *v == rules.variant && *d == rules.draw_mode)
+        {
+            Some(index) => index,
+            None => {
+                self.entries
+                    .push((rules.variant, rules.draw_mode, GameStats::default()));
+                self.entries.len() - 1
+            }
+        };
+
+        self.entries[index].2.add(won, num_moves, elapsed, score);
+
+        if let Some(path) = &self.path {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+
+            fs::write(path, self.to_string())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (variant, draw_mode, stats) in self.iter() {
+            writeln!(f, "[{variant} draw {draw_mode}]")?;
+            writeln!(f, "played {}", stats.played)?;
+            writeln!(f, "won {}", stats.won)?;
+            writeln!(f, "current-streak {}", stats.current_streak)?;
+            writeln!(f, "best-streak {}", stats.best_streak)?;
+
+            if let Some(fastest_win) = stats.fastest_win {
+                writeln!(f, "fastest-win {}", fastest_win.as_secs())?;
+            }
+            if let Some(fewest_moves) = stats.fewest_moves {
+                writeln!(f, "fewest-moves {fewest_moves}")?;
+            }
+            if let Some(best_score) = stats.best_score {
+                writeln!(f, "best-score {best_score}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/**
+ * Read as many statistics as possible, skipping lines which don't make sense.
+ */
+fn parse_entries(text: &str) -> Vec<(Variant, DrawMode, GameStats)> {
+    let mut entries: Vec<(Variant, DrawMode, GameStats)> = Vec::new();
+    let mut current = None;
+
+    for line in text.lines().map(str::trim) {
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            current = parse_section(section);
+
+            if let Some((variant, draw_mode)) = current {
+                entries.push((variant, draw_mode, GameStats::default()));
+            }
+            continue;
+        }
+
+        let stats = match (current, entries.last_mut()) {
+            (Some(_), Some((_, _, stats))) => stats,
+            _ => continue,
+        };
+
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        let value = value.trim();
+        let count = || value.parse::<u32>().ok();
+
+        match key {
+            "played" => stats.played = count().unwrap_or_default(),
+            "won" => stats.won = count().unwrap_or_default(),
+            "current-streak" => stats.current_streak = count().unwrap_or_default(),
+            "best-streak" => stats.best_streak = count().unwrap_or_default(),
+            "fastest-win" => stats.fastest_win = value.parse().ok().map(Duration::from_secs),
+            "fewest-moves" => stats.fewest_moves = count(),
+            "best-score" => stats.best_score = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    entries
+}
+
+fn parse_section(section: &str) -> Option<(Variant, DrawMode)> {
+    match section.split_whitespace().collect::<Vec<_>>()[..] {
+        [variant, "draw", draw_mode] => Some((variant.parse().ok()?, draw_mode.parse().ok()?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_track_of_streaks_and_bests() {
+        let mut stats = GameStats::default();
+
+        stats.add(true, 120, Duration::from_secs(300), 4000);
+        stats.add(true, 100, Duration::from_secs(400), 3500);
+        stats.add(false, 50, Duration::from_secs(60), 200);
+        stats.add(true, 140, Duration::from_secs(250), 4500);
+
+        assert_eq!(
+            stats,
+            GameStats {
+                played: 4,
+                won: 3,
+                current_streak: 1,
+                best_streak: 2,
+                fastest_win: Some(Duration::from_secs(250)),
+                fewest_moves: Some(100),
+                best_score: Some(4500),
+            }
+        );
+        assert_eq!(stats.get_win_rate(), Some(75));
+    }
+
+    #[test]
+    fn only_counts_won_games_towards_bests() {
+        let mut stats = GameStats::default();
+
+        stats.add(false, 10, Duration::from_secs(5), -52);
+
+        assert_eq!(stats.fastest_win, None);
+        assert_eq!(stats.fewest_moves, None);
+        assert_eq!(stats.best_score, Some(-52));
+        assert_eq!(GameStats::default().get_win_rate(), None);
+    }
+
+    #[test]
+    fn reads_back_written_stats() {
+        let mut won = GameStats::default();
+        won.add(true, 98, Duration::from_secs(312), 4521);
+        won.add(false, 30, Duration::from_secs(100), 120);
+
+        let mut lost = GameStats::default();
+        lost.add(false, 12, Duration::from_secs(40), -37);
+
+        let stats = Stats {
+            entries: vec![
+                (Variant::Klondike, DrawMode::One, won),
+                (Variant::Vegas, DrawMode::Three, lost),
+            ],
+            path: None,
+        };
+
+        assert_eq!(parse_entries(&stats.to_string()), stats.entries);
+    }
+
+    #[test]
+    fn skips_unreadable_lines() {
+        let text = "[klondike draw 2]\nplayed 4\n[vegas draw 1]\nplayed x\nwon 2\nstreak 3\n";
+
+        let expected = GameStats {
+            won: 2,
+            ..GameStats::default()
+        };
+
+        assert_eq!(
+            parse_entries(text),
+            vec![(Variant::Vegas, DrawMode::One, expected)]
+        );
+    }
+}
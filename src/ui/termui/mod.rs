@@ -1,4 +1,5 @@
 mod termion_impl;
+mod theme;
 mod ui;
 mod widgets;
 
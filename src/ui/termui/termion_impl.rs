@@ -1,4 +1,8 @@
-use std::{error::Error, io};
+use std::{
+    error::Error,
+    io, thread,
+    time::{Duration, Instant},
+};
 
 use termion::{
     cursor::HideCursor, event::Key, input::TermRead, raw::IntoRawMode, screen::IntoAlternateScreen,
@@ -8,9 +12,24 @@ use tui::{
     Terminal,
 };
 
-use crate::{domain::Action, infrastructure::Container};
+use crate::{
+    domain::{Action, GameOutcome, Replay, StateMachine},
+    infrastructure::Container,
+};
+
+use super::{theme::Theme, ui};
 
-use super::ui;
+const RESUME_DIALOG: &str = "\nResume your unfinished game?\n\n[y] Resume   [n] New deal";
+
+/**
+ * Delays between moves when auto-playing a replay, from slowest to fastest.
+ */
+const REPLAY_DELAYS_MS: [u64; 5] = [2000, 1000, 500, 250, 100];
+
+/**
+ * Delay between cards played to the foundations when auto-completing.
+ */
+const AUTO_COMPLETE_DELAY_MS: u64 = 100;
 
 pub fn run(container: &Container) -> Result<(), Box<dyn Error>> {
     let stdout = io::stdout()
@@ -21,82 +40,308 @@ pub fn run(container: &Container) -> Result<(), Box<dyn Error>> {
     let backend = TermionBackend::new(stdout);
     let terminal = Terminal::new(backend).unwrap();
 
-    run_app(terminal, container)
+    if container.get_config().replay.is_some() {
+        run_replay(terminal, container)
+    } else {
+        run_app(terminal, container)
+    }
 }
 
 fn run_app<B: Backend>(
     mut terminal: Terminal<B>,
     container: &Container,
 ) -> Result<(), Box<dyn Error>> {
-    let stdin = io::stdin().lock();
+    // Keys are read without blocking, so that the game clock keeps ticking meanwhile.
+    let mut keys = termion::async_stdin().keys();
 
     let mut dirty = true;
-    let mut keys = stdin.keys();
+    let mut message: Option<String> = None;
+    let mut unfinished_game = container.find_unfinished_game();
+    let mut shown_elapsed = Duration::ZERO;
+    let mut showing_stats = false;
+    // Whether the game was already paused before showing statistics.
+    let mut was_paused = false;
+    let mut auto_completing = false;
+    let mut last_step_at = Instant::now();
     let board = container.get_board();
     let state_machine = container.get_state_machine();
 
     loop {
-        if dirty {
-            terminal.draw(|f| ui::draw(f, container))?;
+        if dirty && showing_stats {
+            let config = container.get_config();
+            let theme = Theme::new(config.theme, config.ascii);
+            let stats = container.get_stats();
+            terminal.draw(|f| ui::draw_stats(f, &stats.borrow(), theme))?;
+            dirty = false;
+        } else if dirty {
+            let dialog = unfinished_game.as_ref().map(|_| RESUME_DIALOG);
+            terminal.draw(|f| ui::draw(f, container, message.as_deref(), dialog))?;
+            shown_elapsed = state_machine.borrow().get_elapsed();
             dirty = false;
         }
 
-        if let Some(key) = keys.next() {
-            match key? {
-                Key::Char('q') => break,
-                Key::Char(' ') => {
-                    if state_machine
-                        .borrow_mut()
-                        .handle(Action::Act(&mut board.borrow_mut()))
-                    {
-                        dirty = true;
-                    }
-                }
-                Key::Char('\n') => {
-                    if state_machine
+        let key = match keys.next() {
+            Some(key) => key?,
+            None => {
+                let delay = Duration::from_millis(AUTO_COMPLETE_DELAY_MS);
+
+                if auto_completing && last_step_at.elapsed() >= delay {
+                    let result = state_machine
                         .borrow_mut()
-                        .handle(Action::Build(&mut board.borrow_mut()))
-                    {
-                        dirty = true;
+                        .handle(Action::AutoComplete(&mut board.borrow_mut()));
+
+                    if let Err(err) = result {
+                        message = Some(err.to_string());
                     }
-                }
-                Key::Char('w') => {
-                    if state_machine
-                        .borrow_mut()
-                        .handle(Action::Discard(&mut board.borrow_mut()))
-                    {
-                        dirty = true;
+
+                    auto_completing = result.is_ok()
+                        && state_machine.borrow().get_outcome() == GameOutcome::InProgress;
+                    last_step_at = Instant::now();
+
+                    if let Err(err) = container.finish_if_won() {
+                        message = Some(err.to_string());
                     }
-                }
-                Key::Left => {
-                    state_machine
-                        .borrow_mut()
-                        .handle(Action::TargetPrevious(&mut board.borrow_mut()));
+
                     dirty = true;
+                    continue;
                 }
-                Key::Right => {
-                    state_machine
-                        .borrow_mut()
-                        .handle(Action::TargetNext(&mut board.borrow_mut()));
-                    dirty = true;
+
+                // Redraw once the clock shows another second.
+                let elapsed = state_machine.borrow().get_elapsed();
+                dirty = elapsed.as_secs() != shown_elapsed.as_secs();
+
+                if !dirty {
+                    thread::sleep(Duration::from_millis(10));
                 }
-                Key::Up => {
-                    if state_machine
-                        .borrow_mut()
-                        .handle(Action::IncreaseRange(&mut board.borrow_mut()))
-                    {
-                        dirty = true;
+
+                continue;
+            }
+        };
+
+        if let Some(saved) = unfinished_game.take() {
+            match key {
+                Key::Char('y') | Key::Char('\n') => container.resume(saved),
+                Key::Char('n') | Key::Esc => {
+                    if let Err(err) = container.abandon(saved) {
+                        message = Some(err.to_string());
                     }
                 }
-                Key::Down => {
-                    if state_machine.borrow_mut().handle(Action::DecreaseRange) {
-                        dirty = true;
+                // Quit without overwriting the unfinished game.
+                Key::Char('q') => return Ok(()),
+                _ => unfinished_game = Some(saved),
+            }
+
+            dirty = true;
+            continue;
+        }
+
+        if showing_stats {
+            match key {
+                Key::Char('q') => break,
+                Key::Char('s') | Key::Esc => {
+                    if !was_paused {
+                        state_machine.borrow_mut().resume();
                     }
+
+                    showing_stats = false;
+                    dirty = true;
                 }
                 _ => {}
             }
+
+            continue;
+        }
+
+        if key == Key::Char('s') {
+            // Looking at statistics doesn't count towards the game's time.
+            let mut state_machine = state_machine.borrow_mut();
+            was_paused = state_machine.is_paused();
+            state_machine.pause();
+
+            showing_stats = true;
+            dirty = true;
+            continue;
+        }
+
+        let outcome = state_machine.borrow().get_outcome();
+
+        if key == Key::Char('n') && outcome != GameOutcome::InProgress {
+            message = container.new_game().err().map(|err| err.to_string());
+            dirty = true;
+            continue;
+        }
+
+        if key == Key::Char('r') && outcome == GameOutcome::Stuck {
+            message = container.restart().err().map(|err| err.to_string());
+            dirty = true;
+            continue;
+        }
+
+        if key == Key::Esc && outcome == GameOutcome::Stuck {
+            state_machine.borrow_mut().dismiss_stuck();
+            dirty = true;
+            continue;
+        }
+
+        if key == Key::Char('p') {
+            let mut state_machine = state_machine.borrow_mut();
+
+            if state_machine.is_paused() {
+                state_machine.resume();
+            } else {
+                state_machine.pause();
+            }
+
+            dirty = true;
+            continue;
+        }
+
+        if key == Key::Char('e') {
+            message = Some(match container.export() {
+                Ok(path) => format!("Game exported to {}", path.display()),
+                Err(err) => format!("Can't export the game: {err}"),
+            });
+            dirty = true;
+            continue;
+        }
+
+        let mut state_machine = state_machine.borrow_mut();
+
+        let changed = match key {
+            Key::Char('q') => break,
+            Key::Char(' ') => state_machine.handle(Action::Act(&mut board.borrow_mut())),
+            Key::Char('\n') => state_machine.handle(Action::Build(&mut board.borrow_mut())),
+            Key::Char('w') => state_machine.handle(Action::Deal(&mut board.borrow_mut())),
+            Key::Char('u') => state_machine.handle(Action::Undo(&mut board.borrow_mut())),
+            Key::Ctrl('r') => state_machine.handle(Action::Redo(&mut board.borrow_mut())),
+            Key::Char('h') => state_machine.handle(Action::Hint(&board.borrow())),
+            Key::Char('a') => {
+                // Play the first card right away, and the others one after another.
+                auto_completing = board.borrow().can_auto_complete();
+                last_step_at = Instant::now();
+                state_machine.handle(Action::AutoComplete(&mut board.borrow_mut()))
+            }
+            Key::Left => state_machine.handle(Action::TargetPrevious(&board.borrow())),
+            Key::Right => state_machine.handle(Action::TargetNext(&board.borrow())),
+            Key::Up => state_machine.handle(Action::IncreaseRange(&board.borrow())),
+            Key::Down => state_machine.handle(Action::DecreaseRange),
+            _ => Ok(false),
+        };
+
+        // Finishing the game needs the state machine.
+        drop(state_machine);
+
+        match changed {
+            Ok(true) => {
+                // Carry on auto-completing, or start doing so if asked to.
+                auto_completing = (auto_completing || container.get_config().auto_complete)
+                    && board.borrow().can_auto_complete();
+
+                message = container.finish_if_won().err().map(|err| err.to_string());
+                dirty = true;
+            }
+            Ok(false) => {}
+            Err(err) => {
+                auto_completing = false;
+                message = Some(err.to_string());
+                dirty = true;
+            }
+        }
+    }
+
+    container.autosave()
+}
+
+fn run_replay<B: Backend>(
+    mut terminal: Terminal<B>,
+    container: &Container,
+) -> Result<(), Box<dyn Error>> {
+    // Keys are read without blocking, so that moves can be auto-played meanwhile.
+    let mut keys = termion::async_stdin().keys();
+
+    let config = container.get_config();
+    let theme = Theme::new(config.theme, config.ascii);
+
+    let mut replay = Replay::new(&container.get_board().borrow());
+    let mut dirty = true;
+    let mut playing = false;
+    let mut speed = 2;
+    let mut last_step_at = Instant::now();
+
+    loop {
+        if dirty {
+            let state_machine = StateMachine::replaying(replay.get_last_move());
+            let status = get_replay_status(&replay, playing, speed);
+            terminal.draw(|f| ui::draw_replay(f, &replay, &state_machine, theme, &status))?;
+            dirty = false;
+        }
+
+        let key = match keys.next() {
+            Some(key) => key?,
+            None => {
+                let delay = Duration::from_millis(REPLAY_DELAYS_MS[speed]);
+
+                if playing && last_step_at.elapsed() >= delay {
+                    replay.step_forward();
+                    playing = !replay.is_at_end();
+                    last_step_at = Instant::now();
+                    dirty = true;
+                } else {
+                    thread::sleep(Duration::from_millis(10));
+                }
+
+                continue;
+            }
+        };
+
+        dirty = match key {
+            Key::Char('q') => break,
+            Key::Right => replay.step_forward(),
+            Key::Left => replay.step_back(),
+            Key::Home => replay.jump_to_start(),
+            Key::End => replay.jump_to_end(),
+            Key::Char(' ') => {
+                // Playing from the end starts over.
+                if !playing && replay.is_at_end() {
+                    replay.jump_to_start();
+                }
+                playing = !playing;
+                last_step_at = Instant::now();
+                true
+            }
+            Key::Char('+') | Key::Up if speed < REPLAY_DELAYS_MS.len() - 1 => {
+                speed += 1;
+                true
+            }
+            Key::Char('-') | Key::Down if speed > 0 => {
+                speed -= 1;
+                true
+            }
+            _ => false,
+        };
+
+        // Stepping by hand takes over from auto-play.
+        if playing && matches!(key, Key::Right | Key::Left | Key::Home | Key::End) {
+            playing = false;
+            dirty = true;
         }
     }
 
     Ok(())
 }
+
+fn get_replay_status(replay: &Replay, playing: bool, speed: usize) -> String {
+    let moves_per_second = 1000.0 / REPLAY_DELAYS_MS[speed] as f64;
+
+    let state = if playing {
+        format!("playing at {moves_per_second} moves/s")
+    } else {
+        "paused".to_string()
+    };
+
+    format!(
+        "Move {}/{}, {state}\n[</>] Step  [Home/End] Jump  [Space] Play  [+/-] Speed  [q] Quit",
+        replay.get_index(),
+        replay.get_num_moves(),
+    )
+}
@@ -0,0 +1,116 @@
+use tui::style::Color;
+
+use crate::infrastructure::ThemeName;
+
+/**
+ * Characters used to draw cards, so that terminals without Unicode support can be used.
+ */
+pub struct Glyphs {
+    pub card_top: &'static str,
+    pub card_bottom: &'static str,
+    pub card_side: &'static str,
+    pub cover: &'static str,
+    pub slot_top: &'static str,
+    pub slot_bottom: &'static str,
+    pub slot_side: &'static str,
+    pub hearts: &'static str,
+    pub diamonds: &'static str,
+    pub spades: &'static str,
+    pub clubs: &'static str,
+    pub cursor: &'static str,
+    pub reload: [char; 2],
+    pub passes_left: char,
+    pub no_passes_left: char,
+}
+
+static UNICODE_GLYPHS: Glyphs = Glyphs {
+    card_top: "┌───┐",
+    card_bottom: "└───┘",
+    card_side: "│",
+    cover: "▚▚▚",
+    slot_top: "┌╌╌╌┐",
+    slot_bottom: "└╌╌╌┘",
+    slot_side: "╎",
+    hearts: "♥",
+    diamonds: "♦",
+    spades: "♠",
+    clubs: "♣",
+    cursor: "^",
+    reload: ['↱', '↲'],
+    passes_left: '↻',
+    no_passes_left: '✕',
+};
+
+static ASCII_GLYPHS: Glyphs = Glyphs {
+    card_top: "+---+",
+    card_bottom: "+---+",
+    card_side: "|",
+    cover: "###",
+    slot_top: ".   .",
+    slot_bottom: "'   '",
+    slot_side: ":",
+    hearts: "H",
+    diamonds: "D",
+    spades: "S",
+    clubs: "C",
+    cursor: "^",
+    reload: ['>', '<'],
+    passes_left: '@',
+    no_passes_left: 'x',
+};
+
+#[derive(Clone, Copy)]
+pub struct Theme {
+    pub focused: Color,
+    pub picked: Color,
+    pub hinted: Color,
+    pub cover: Color,
+    pub red: Color,
+    pub black: Color,
+    pub glyphs: &'static Glyphs,
+    pub ascii: bool,
+}
+
+impl Theme {
+    pub fn new(name: ThemeName, ascii: bool) -> Self {
+        let glyphs = if ascii {
+            &ASCII_GLYPHS
+        } else {
+            &UNICODE_GLYPHS
+        };
+
+        match name {
+            ThemeName::Classic => Self {
+                focused: Color::Cyan,
+                picked: Color::Yellow,
+                hinted: Color::Magenta,
+                cover: Color::LightBlue,
+                red: Color::Red,
+                black: Color::Black,
+                glyphs,
+                ascii,
+            },
+            // Black suits would be invisible on a dark background.
+            ThemeName::Dark => Self {
+                focused: Color::LightCyan,
+                picked: Color::LightYellow,
+                hinted: Color::LightMagenta,
+                cover: Color::Blue,
+                red: Color::LightRed,
+                black: Color::White,
+                glyphs,
+                ascii,
+            },
+            ThemeName::Mono => Self {
+                focused: Color::Reset,
+                picked: Color::Reset,
+                hinted: Color::Reset,
+                cover: Color::Reset,
+                red: Color::Reset,
+                black: Color::Reset,
+                glyphs,
+                ascii,
+            },
+        }
+    }
+}
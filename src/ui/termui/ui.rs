@@ -1,22 +1,225 @@
+use std::time::Duration;
+
 use tui::{
     backend::Backend,
-    layout::{Constraint, Direction, Layout},
-    widgets::{Block, Borders},
+    layout::Alignment,
+    layout::{Constraint, Direction, Layout, Rect},
+    widgets::{Block, Borders, Clear, Paragraph, Row, Table},
     Frame,
 };
 
-use crate::infrastructure::Container;
+use crate::{
+    domain::{get_score, get_time_bonus, Board, GameOutcome, Replay, StateMachine, Variant},
+    infrastructure::{Container, Stats},
+};
+
+use super::{theme::Theme, widgets::make_app_widget};
 
-use super::widgets::make_app_widget;
+const VICTORY_DIALOG: &str = "\nYou won!\n\n[n] New deal   [q] Quit";
+const STATS_HEADER: [&str; 9] = [
+    "Game",
+    "Played",
+    "Won",
+    "Win %",
+    "Streak",
+    "Best streak",
+    "Fastest win",
+    "Fewest moves",
+    "Best score",
+];
+const STATS_WIDTHS: [Constraint; 9] = [
+    Constraint::Length(16),
+    Constraint::Length(7),
+    Constraint::Length(5),
+    Constraint::Length(6),
+    Constraint::Length(7),
+    Constraint::Length(12),
+    Constraint::Length(12),
+    Constraint::Length(13),
+    Constraint::Length(10),
+];
+const STUCK_DIALOG: &str =
+    "\nNo move can make progress.\n\n[u] Undo   [n] New deal\n[r] Restart   [Esc] Play on";
+const PAUSED_DIALOG: &str = "\nPaused\n\n[p] Resume   [q] Quit";
 
-pub fn draw<B: Backend>(f: &mut Frame<B>, container: &Container) {
+pub fn draw<B: Backend>(
+    f: &mut Frame<B>,
+    container: &Container,
+    message: Option<&str>,
+    dialog: Option<&str>,
+) {
     let board_ref = container.get_board();
     let state_machine_ref = container.get_state_machine();
 
     let board = board_ref.borrow();
     let state_machine = state_machine_ref.borrow();
 
-    let app = make_app_widget(&board, &state_machine);
+    let config = container.get_config();
+    let theme = Theme::new(config.theme, config.ascii);
+
+    let title = match board.get_deal_number() {
+        Some(deal_number) => format!("Solitaire (deal #{deal_number})"),
+        None => "Solitaire".to_string(),
+    };
+
+    let status = format!(
+        "{}   Moves: {}   Time: {}",
+        get_score_status(&board, &state_machine, container),
+        state_machine.get_num_moves(),
+        format_duration(state_machine.get_elapsed()),
+    );
+
+    let text = match message {
+        Some(message) => format!("{status}\n{message}"),
+        None => status,
+    };
+
+    let area = draw_board(f, &board, &state_machine, theme, title, Some(&text));
+
+    if let Some(text) = dialog {
+        draw_dialog(f, area, text, &theme);
+    } else if state_machine.is_paused() {
+        draw_dialog(f, area, PAUSED_DIALOG, &theme);
+    } else {
+        match state_machine.get_outcome() {
+            GameOutcome::Won => draw_dialog(f, area, VICTORY_DIALOG, &theme),
+            GameOutcome::Stuck => draw_dialog(f, area, STUCK_DIALOG, &theme),
+            GameOutcome::InProgress => {}
+        }
+    }
+}
+
+/**
+ * Score or balance shown beneath the board during a game.
+ */
+fn get_score_status(board: &Board, state_machine: &StateMachine, container: &Container) -> String {
+    if board.get_rules().variant == Variant::Vegas {
+        return format!("Balance: {}", format_money(container.get_balance()));
+    }
+
+    let score = get_score(board);
+
+    if board.get_outcome() != GameOutcome::Won {
+        return format!("Score: {score}");
+    }
+
+    match get_time_bonus(state_machine.get_elapsed()) {
+        0 => format!("Score: {score}"),
+        bonus => format!("Score: {} ({score} + {bonus} time bonus)", score + bonus),
+    }
+}
+
+fn format_money(amount: i64) -> String {
+    if amount < 0 {
+        format!("-${}", -amount)
+    } else {
+        format!("${amount}")
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+
+    if secs < 3600 {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    } else {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    }
+}
+
+pub fn draw_stats<B: Backend>(f: &mut Frame<B>, stats: &Stats, theme: Theme) {
+    let block = Block::default()
+        .title("Statistics")
+        .borders(get_borders(&theme));
+    let area = block.inner(f.size());
+
+    f.render_widget(block, f.size());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+        .split(area);
+
+    let rows = stats
+        .iter()
+        .map(|&(variant, draw_mode, stats)| {
+            let best_score = stats.best_score.map(|score| match variant {
+                Variant::Vegas => format_money(score),
+                Variant::Klondike => score.to_string(),
+            });
+
+            Row::new(vec![
+                format!("{} (draw {draw_mode})", capitalize(&variant.to_string())),
+                stats.played.to_string(),
+                stats.won.to_string(),
+                format_optional(stats.get_win_rate()),
+                stats.current_streak.to_string(),
+                stats.best_streak.to_string(),
+                format_optional(stats.fastest_win.map(format_duration)),
+                format_optional(stats.fewest_moves),
+                format_optional(best_score),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    if rows.is_empty() {
+        f.render_widget(Paragraph::new("No games played yet."), chunks[0]);
+    } else {
+        let table = Table::new(rows)
+            .header(Row::new(STATS_HEADER.to_vec()).bottom_margin(1))
+            .widths(&STATS_WIDTHS)
+            .column_spacing(1);
+
+        f.render_widget(table, chunks[0]);
+    }
+
+    f.render_widget(Paragraph::new("[s] Back   [q] Quit"), chunks[1]);
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn format_optional(value: Option<impl ToString>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| value.to_string())
+}
+
+pub fn draw_replay<B: Backend>(
+    f: &mut Frame<B>,
+    replay: &Replay,
+    state_machine: &StateMachine,
+    theme: Theme,
+    status: &str,
+) {
+    let board = replay.get_board();
+
+    let title = match board.get_deal_number() {
+        Some(deal_number) => format!("Replay (deal #{deal_number})"),
+        None => "Replay".to_string(),
+    };
+
+    draw_board(f, board, state_machine, theme, title, Some(status));
+}
+
+/**
+ * Draw the board within a block, returning the area of the block.
+ */
+fn draw_board<B: Backend>(
+    f: &mut Frame<B>,
+    board: &Board,
+    state_machine: &StateMachine,
+    theme: Theme,
+    title: String,
+    message: Option<&str>,
+) -> Rect {
+    let app = make_app_widget(board, state_machine, theme);
+
+    let app_height = app.get_height();
 
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -24,9 +227,52 @@ pub fn draw<B: Backend>(f: &mut Frame<B>, container: &Container) {
         .split(f.size());
 
     f.render_widget(
-        Block::default().title("Solitaire").borders(Borders::ALL),
+        Block::default().title(title).borders(get_borders(&theme)),
         chunks[0],
     );
 
     f.render_widget(app, chunks[0]);
+
+    if let Some(message) = message {
+        // Show the message on the lines beneath the board, inside the block.
+        let area = Rect::new(
+            chunks[0].x + 2,
+            chunks[0].y + app_height,
+            chunks[0].width.saturating_sub(4),
+            message.lines().count() as u16,
+        )
+        .intersection(chunks[0]);
+
+        f.render_widget(Paragraph::new(message), area);
+    }
+
+    chunks[0]
+}
+
+fn draw_dialog<B: Backend>(f: &mut Frame<B>, area: Rect, text: &str, theme: &Theme) {
+    let width = 32.min(area.width);
+    let height = 7.min(area.height);
+    let area = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(get_borders(theme))),
+        area,
+    );
+}
+
+fn get_borders(theme: &Theme) -> Borders {
+    // Box drawing characters are the only ones tui knows how to draw borders with.
+    if theme.ascii {
+        Borders::NONE
+    } else {
+        Borders::ALL
+    }
 }
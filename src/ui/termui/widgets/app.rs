@@ -1,19 +1,22 @@
-use crate::domain::{Board, Pile, StateMachine, Target, TargetStatus};
+use crate::domain::{Board, DrawMode, Pile, StateMachine, Target, TargetStatus};
+
+use crate::ui::termui::theme::{Glyphs, Theme};
 
 use super::{
     card::CardAppearance,
     layout::{HorizontalWidget, VerticalWidget},
-    pile::{FannedPileWidget, StackedPileWidget},
+    pile::{FannedPileWidget, SpreadPileWidget, StackedPileWidget},
 };
 
 pub fn make_app_widget<'a>(
     board: &'a Board,
     state_machine: &'a StateMachine,
+    theme: Theme,
 ) -> HorizontalWidget<'a> {
     HorizontalWidget::new(vec![
-        make_hands_widget(board.get_stock(), board.get_waste(), &state_machine).into(),
-        make_tableau_widget(board.get_tableau(), &state_machine).into(),
-        make_foundations_widget(board.get_foundations()).into(),
+        make_hands_widget(board, state_machine, theme).into(),
+        make_tableau_widget(board.get_tableau(), state_machine, theme).into(),
+        make_foundations_widget(board.get_foundations(), state_machine, theme).into(),
     ])
     .gap(5)
     .horizontal_margin(3)
@@ -21,33 +24,59 @@ pub fn make_app_widget<'a>(
 }
 
 fn make_hands_widget<'a>(
-    stock: &'a Pile,
-    waste: &'a Pile,
+    board: &'a Board,
     state_machine: &'a StateMachine,
+    theme: Theme,
 ) -> VerticalWidget<'a> {
-    let stock_appearance = state_machine
-        .get_status_of(Target::Stock)
-        .map(|status| match status {
-            TargetStatus::Current { .. } => CardAppearance::Focused,
-            TargetStatus::Picked { .. } => CardAppearance::Picked,
-        });
+    let stock_appearance = get_stacked_appearance(state_machine, Target::Stock);
+    let waste_appearance = get_stacked_appearance(state_machine, Target::Waste);
+
+    let waste = board.get_waste();
+
+    let waste_widget = match board.get_rules().draw_mode {
+        DrawMode::One => StackedPileWidget::new(waste, waste_appearance, theme).into(),
+        mode => SpreadPileWidget::new(waste, waste_appearance, mode.num_cards(), theme).into(),
+    };
 
     VerticalWidget::new(vec![
-        StackedPileWidget::new(stock, stock_appearance)
-            .empty_content(['↱', '↲'])
+        StackedPileWidget::new(board.get_stock(), stock_appearance, theme)
+            .empty_content(get_reload_content(
+                board.get_remaining_passes(),
+                theme.glyphs,
+            ))
             .into(),
-        StackedPileWidget::new(waste, None).into(),
+        waste_widget,
     ])
     .gap(1)
 }
 
+fn get_reload_content(remaining_passes: Option<u32>, glyphs: &Glyphs) -> [char; 2] {
+    match remaining_passes {
+        None => glyphs.reload,
+        Some(0) => [glyphs.no_passes_left, ' '],
+        // Show how many more times the stock can be reloaded.
+        Some(n) => [glyphs.passes_left, char::from_digit(n, 10).unwrap_or('+')],
+    }
+}
+
+fn get_stacked_appearance(state_machine: &StateMachine, target: Target) -> Option<CardAppearance> {
+    state_machine
+        .get_status_of(target)
+        .map(|status| match status {
+            TargetStatus::Current { .. } => CardAppearance::Focused,
+            TargetStatus::Picked { .. } => CardAppearance::Picked,
+            TargetStatus::Hinted { .. } => CardAppearance::Hinted,
+        })
+}
+
 fn make_tableau_widget<'a>(
-    piles: &'a Vec<Pile>,
+    piles: &'a [Pile],
     state_machine: &'a StateMachine,
+    theme: Theme,
 ) -> HorizontalWidget<'a> {
     HorizontalWidget::new(
         piles
-            .into_iter()
+            .iter()
             .enumerate()
             .map(|(index, pile)| {
                 let pile_appearance =
@@ -60,20 +89,31 @@ fn make_tableau_widget<'a>(
                             TargetStatus::Picked { num_cards } => {
                                 (CardAppearance::Picked, num_cards)
                             }
+                            TargetStatus::Hinted { num_cards } => {
+                                (CardAppearance::Hinted, num_cards)
+                            }
                         });
 
-                FannedPileWidget::new(pile, pile_appearance).into()
+                FannedPileWidget::new(pile, pile_appearance, theme).into()
             })
             .collect::<Vec<_>>(),
     )
     .gap(2)
 }
 
-fn make_foundations_widget(foundations: &Vec<Pile>) -> VerticalWidget {
+fn make_foundations_widget<'a>(
+    foundations: &'a [Pile],
+    state_machine: &'a StateMachine,
+    theme: Theme,
+) -> VerticalWidget<'a> {
     VerticalWidget::new(
         foundations
             .iter()
-            .map(|pile| StackedPileWidget::new(pile, None).into())
+            .enumerate()
+            .map(|(index, pile)| {
+                let appearance = get_stacked_appearance(state_machine, Target::Foundation(index));
+                StackedPileWidget::new(pile, appearance, theme).into()
+            })
             .collect::<Vec<_>>(),
     )
 }
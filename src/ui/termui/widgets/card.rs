@@ -4,26 +4,36 @@ use tui::style::{Color, Style};
 use tui::widgets::Widget;
 
 use crate::domain::{Card, Rank, Suit};
-
-static FOCUSED_COLOR: Color = Color::Cyan;
-static PICKED_COLOR: Color = Color::Yellow;
-static COVER_COLOR: Color = Color::LightBlue;
+use crate::ui::termui::theme::Theme;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum CardAppearance {
     Focused,
     Picked,
+    Hinted,
+}
+
+pub struct SuitWidget {
+    suit: Suit,
+    theme: Theme,
 }
 
-impl Widget for Suit {
+impl SuitWidget {
+    pub fn new(suit: Suit, theme: Theme) -> Self {
+        Self { suit, theme }
+    }
+}
+
+impl Widget for SuitWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let Rect { x, y, .. } = area;
+        let glyphs = self.theme.glyphs;
 
-        let (symbol, fg) = match self {
-            Suit::Heart => ("♥", Color::Red),
-            Suit::Diamond => ("♦", Color::Red),
-            Suit::Spades => ("♠", Color::Black),
-            Suit::Club => ("♣", Color::Black),
+        let (symbol, fg) = match self.suit {
+            Suit::Heart => (glyphs.hearts, self.theme.red),
+            Suit::Diamond => (glyphs.diamonds, self.theme.red),
+            Suit::Spades => (glyphs.spades, self.theme.black),
+            Suit::Club => (glyphs.clubs, self.theme.black),
         };
 
         buf.set_string(x, y, symbol, Style::default().fg(fg));
@@ -33,16 +43,16 @@ impl Widget for Suit {
 impl Widget for Rank {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let Rect { x, y, .. } = area;
+        buf.set_string(x, y, format!("{:>2}", self.to_string()), Style::default());
+    }
+}
 
-        let symbol = match self.0 {
-            13 => "K".into(),
-            12 => "Q".into(),
-            11 => "J".into(),
-            1 => "A".into(),
-            s => s.to_string(),
-        };
-
-        buf.set_string(x, y, &format!("{symbol:>2}"), Style::default());
+fn get_color(appearance: Option<CardAppearance>, theme: &Theme) -> Color {
+    match appearance {
+        Some(CardAppearance::Focused) => theme.focused,
+        Some(CardAppearance::Picked) => theme.picked,
+        Some(CardAppearance::Hinted) => theme.hinted,
+        None => Color::Reset,
     }
 }
 
@@ -50,11 +60,16 @@ impl Widget for Rank {
 pub struct CardWidget {
     card: Card,
     appearance: Option<CardAppearance>,
+    theme: Theme,
 }
 
 impl CardWidget {
-    pub fn new(card: Card, appearance: Option<CardAppearance>) -> Self {
-        Self { card, appearance }
+    pub fn new(card: Card, appearance: Option<CardAppearance>, theme: Theme) -> Self {
+        Self {
+            card,
+            appearance,
+            theme,
+        }
     }
 
     pub fn width() -> u16 {
@@ -73,46 +88,47 @@ impl CardWidget {
 impl Widget for CardWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let Rect { x, y, .. } = area;
+        let glyphs = self.theme.glyphs;
 
         // Levitation effect
         let x = match self.appearance {
-            Some(CardAppearance::Focused) => x + 1,
-            Some(CardAppearance::Picked) => x + 1,
+            Some(_) => x + 1,
             None => x,
         };
 
-        let fg = match self.appearance {
-            Some(CardAppearance::Focused) => FOCUSED_COLOR,
-            Some(CardAppearance::Picked) => PICKED_COLOR,
-            None => Color::Reset,
-        };
+        let style = Style::default().fg(get_color(self.appearance, &self.theme));
 
-        buf.set_string(x, y, "┌───┐", Style::default().fg(fg));
+        buf.set_string(x, y, glyphs.card_top, style);
 
         if self.card.is_visible() {
             // | ♥ K |
-            buf.set_string(x, y + 1, "│", Style::default().fg(fg));
-            self.card.suit.render(Rect::new(x + 1, y + 1, 1, 1), buf);
+            buf.set_string(x, y + 1, glyphs.card_side, style);
+            SuitWidget::new(self.card.suit, self.theme).render(Rect::new(x + 1, y + 1, 1, 1), buf);
             self.card.rank.render(Rect::new(x + 2, y + 1, 2, 1), buf);
-            buf.set_string(x + 4, y + 1, "│", Style::default().fg(fg));
+            buf.set_string(x + 4, y + 1, glyphs.card_side, style);
 
             // |   ♥ |
-            buf.set_string(x, y + 2, "│", Style::default().fg(fg));
-            self.card.suit.render(Rect::new(x + 3, y + 2, 1, 1), buf);
-            buf.set_string(x + 4, y + 2, "│", Style::default().fg(fg));
+            buf.set_string(x, y + 2, glyphs.card_side, style);
+            SuitWidget::new(self.card.suit, self.theme).render(Rect::new(x + 3, y + 2, 1, 1), buf);
+            buf.set_string(x + 4, y + 2, glyphs.card_side, style);
         } else {
             for dy in &[1, 2] {
                 // | ▚▚▚ |
-                buf.set_string(x, y + dy, "│", Style::default().fg(fg));
-                buf.set_string(x + 1, y + dy, "▚▚▚", Style::default().fg(COVER_COLOR));
-                buf.set_string(x + 4, y + dy, "│", Style::default().fg(fg));
+                buf.set_string(x, y + dy, glyphs.card_side, style);
+                buf.set_string(
+                    x + 1,
+                    y + dy,
+                    glyphs.cover,
+                    Style::default().fg(self.theme.cover),
+                );
+                buf.set_string(x + 4, y + dy, glyphs.card_side, style);
             }
         }
 
-        buf.set_string(x, y + 3, "└───┘", Style::default().fg(fg));
+        buf.set_string(x, y + 3, glyphs.card_bottom, style);
 
         if self.appearance == Some(CardAppearance::Focused) {
-            buf.set_string(x + 2, y + 4, "^", Style::default().fg(fg));
+            buf.set_string(x + 2, y + 4, glyphs.cursor, style);
         }
     }
 }
@@ -120,13 +136,15 @@ impl Widget for CardWidget {
 pub struct EmptySlotWidget {
     appearance: Option<CardAppearance>,
     content: [char; 2],
+    theme: Theme,
 }
 
 impl EmptySlotWidget {
-    pub fn new(appearance: Option<CardAppearance>) -> Self {
+    pub fn new(appearance: Option<CardAppearance>, theme: Theme) -> Self {
         Self {
             appearance,
             content: [' '; 2],
+            theme,
         }
     }
 
@@ -140,15 +158,28 @@ impl Widget for EmptySlotWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let x = area.x;
         let y = area.y;
+        let glyphs = self.theme.glyphs;
 
         let style = Style::default().fg(match self.appearance {
-            Some(CardAppearance::Focused) => FOCUSED_COLOR,
-            _ => Color::Reset,
+            Some(CardAppearance::Picked) => Color::Reset,
+            appearance => get_color(appearance, &self.theme),
         });
 
-        buf.set_string(x, y, "┌╌╌╌┐", style);
-        buf.set_string(x, y + 1, format!("╎ {} ╎", self.content[0]), style);
-        buf.set_string(x, y + 2, format!("╎ {} ╎", self.content[1]), style);
-        buf.set_string(x, y + 3, "└╌╌╌┘", style);
+        let side = glyphs.slot_side;
+
+        buf.set_string(x, y, glyphs.slot_top, style);
+        buf.set_string(
+            x,
+            y + 1,
+            format!("{side} {} {side}", self.content[0]),
+            style,
+        );
+        buf.set_string(
+            x,
+            y + 2,
+            format!("{side} {} {side}", self.content[1]),
+            style,
+        );
+        buf.set_string(x, y + 3, glyphs.slot_bottom, style);
     }
 }
@@ -43,7 +43,7 @@ impl<'a> HorizontalWidget<'a> {
             .iter()
             .enumerate()
             .map(|(index, w)| (index == self.widgets.len() - 1, w))
-            .map(move |(is_last, w)| w.get_width() + is_last.then_some(0).unwrap_or(self.gap))
+            .map(move |(is_last, w)| w.get_width() + if is_last { 0 } else { self.gap })
     }
 
     pub fn get_width(&self) -> u16 {
@@ -69,9 +69,9 @@ impl<'a> Widget for HorizontalWidget<'a> {
             .vertical_margin(self.mv)
             .constraints(
                 self.iter_widths()
-                    .map(|w| Constraint::Length(w))
+                    .map(Constraint::Length)
                     .collect::<Vec<_>>()
-                    .as_ref(),
+                    .as_slice(),
             )
             .split(area);
 
@@ -26,7 +26,7 @@ impl<'a> VerticalWidget<'a> {
             .iter()
             .enumerate()
             .map(|(index, w)| (index == self.widgets.len() - 1, w))
-            .map(move |(is_last, w)| w.get_height() + is_last.then_some(0).unwrap_or(self.gap))
+            .map(move |(is_last, w)| w.get_height() + if is_last { 0 } else { self.gap })
     }
 
     pub fn get_width(&self) -> u16 {
@@ -48,9 +48,9 @@ impl<'a> Widget for VerticalWidget<'a> {
             .direction(Direction::Vertical)
             .constraints(
                 self.iter_heights()
-                    .map(|w| Constraint::Length(w))
+                    .map(Constraint::Length)
                     .collect::<Vec<_>>()
-                    .as_ref(),
+                    .as_slice(),
             )
             .split(area);
 
@@ -1,6 +1,6 @@
 use tui::widgets::Widget;
 
-use crate::ui::termui::widgets::pile::{FannedPileWidget, StackedPileWidget};
+use crate::ui::termui::widgets::pile::{FannedPileWidget, SpreadPileWidget, StackedPileWidget};
 
 use super::{HorizontalWidget, VerticalWidget};
 
@@ -20,6 +20,7 @@ reduced by using [enum_dispatch](https://docs.rs/enum_dispatch/0.3.3/enum_dispat
 pub enum LayoutWidget<'a> {
     StackedPile(StackedPileWidget<'a>),
     FannedPile(FannedPileWidget<'a>),
+    SpreadPile(SpreadPileWidget<'a>),
     Horizontal(HorizontalWidget<'a>),
     Vertical(VerticalWidget<'a>),
 }
@@ -36,6 +37,12 @@ impl<'a> From<FannedPileWidget<'a>> for LayoutWidget<'a> {
     }
 }
 
+impl<'a> From<SpreadPileWidget<'a>> for LayoutWidget<'a> {
+    fn from(w: SpreadPileWidget<'a>) -> Self {
+        LayoutWidget::SpreadPile(w)
+    }
+}
+
 impl<'a> From<HorizontalWidget<'a>> for LayoutWidget<'a> {
     fn from(w: HorizontalWidget<'a>) -> Self {
         LayoutWidget::Horizontal(w)
@@ -53,6 +60,7 @@ impl<'a> LayoutWidget<'a> {
         match self {
             LayoutWidget::StackedPile(w) => w.get_width(),
             LayoutWidget::FannedPile(w) => w.get_width(),
+            LayoutWidget::SpreadPile(w) => w.get_width(),
             LayoutWidget::Horizontal(w) => w.get_width(),
             LayoutWidget::Vertical(w) => w.get_width(),
         }
@@ -62,6 +70,7 @@ impl<'a> LayoutWidget<'a> {
         match self {
             LayoutWidget::StackedPile(w) => w.get_height(),
             LayoutWidget::FannedPile(w) => w.get_height(),
+            LayoutWidget::SpreadPile(w) => w.get_height(),
             LayoutWidget::Horizontal(w) => w.get_height(),
             LayoutWidget::Vertical(w) => w.get_height(),
         }
@@ -73,6 +82,7 @@ impl<'a> Widget for LayoutWidget<'a> {
         match self {
             LayoutWidget::StackedPile(w) => w.render(area, buf),
             LayoutWidget::FannedPile(w) => w.render(area, buf),
+            LayoutWidget::SpreadPile(w) => w.render(area, buf),
             LayoutWidget::Horizontal(w) => w.render(area, buf),
             LayoutWidget::Vertical(w) => w.render(area, buf),
         }
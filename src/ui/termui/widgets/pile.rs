@@ -1,6 +1,7 @@
 use tui::{buffer::Buffer, layout::Rect, widgets::Widget};
 
 use crate::domain::Pile;
+use crate::ui::termui::theme::Theme;
 
 use super::card::{CardAppearance, CardWidget, EmptySlotWidget};
 
@@ -10,11 +11,16 @@ use super::card::{CardAppearance, CardWidget, EmptySlotWidget};
 pub struct FannedPileWidget<'a> {
     pile: &'a Pile,
     appearance: Option<(CardAppearance, usize)>,
+    theme: Theme,
 }
 
 impl<'a> FannedPileWidget<'a> {
-    pub fn new(pile: &'a Pile, appearance: Option<(CardAppearance, usize)>) -> Self {
-        Self { pile, appearance }
+    pub fn new(pile: &'a Pile, appearance: Option<(CardAppearance, usize)>, theme: Theme) -> Self {
+        Self {
+            pile,
+            appearance,
+            theme,
+        }
     }
 
     pub fn get_width(&self) -> u16 {
@@ -35,24 +41,26 @@ impl<'a> FannedPileWidget<'a> {
 impl<'a> Widget for FannedPileWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         if self.pile.is_empty() {
-            let widget =
-                EmptySlotWidget::new(self.appearance.map(|(card_appearance, _)| card_appearance));
+            let widget = EmptySlotWidget::new(
+                self.appearance.map(|(card_appearance, _)| card_appearance),
+                self.theme,
+            );
             widget.render(area, buf);
             return;
         }
 
-        let mut region = area.clone();
+        let mut region = area;
 
         for (index, card) in self.pile.iter().enumerate() {
             let card_appearance = match &self.appearance {
                 Some((card_appearance, size)) => {
-                    let is_in_range = index >= self.pile.len() - size;
-                    is_in_range.then(|| *card_appearance)
+                    let is_in_range = index >= self.pile.len().saturating_sub(*size);
+                    is_in_range.then_some(*card_appearance)
                 }
                 _ => None,
             };
 
-            let widget = CardWidget::new(card.clone(), card_appearance);
+            let widget = CardWidget::new(*card, card_appearance, self.theme);
             widget.render(region, buf);
 
             let is_last = index == self.pile.len() - 1;
@@ -75,14 +83,16 @@ pub struct StackedPileWidget<'a> {
     pile: &'a Pile,
     appearance: Option<CardAppearance>,
     empty_content: [char; 2],
+    theme: Theme,
 }
 
 impl<'a> StackedPileWidget<'a> {
-    pub fn new(pile: &'a Pile, appearance: Option<CardAppearance>) -> Self {
+    pub fn new(pile: &'a Pile, appearance: Option<CardAppearance>, theme: Theme) -> Self {
         Self {
             pile,
             appearance,
             empty_content: [' '; 2],
+            theme,
         }
     }
 
@@ -103,11 +113,74 @@ impl<'a> StackedPileWidget<'a> {
 impl<'a> Widget for StackedPileWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         if let Some(top_card) = self.pile.last() {
-            let card_widget = CardWidget::new(top_card.clone(), self.appearance);
+            let card_widget = CardWidget::new(*top_card, self.appearance, self.theme);
             card_widget.render(area, buf);
         } else {
-            let widget = EmptySlotWidget::new(self.appearance).content(self.empty_content);
+            let widget =
+                EmptySlotWidget::new(self.appearance, self.theme).content(self.empty_content);
             widget.render(area, buf);
         }
     }
 }
+
+/**
+ * Display the topmost cards of a pile spread out as a row, or an empty slot.
+ */
+pub struct SpreadPileWidget<'a> {
+    pile: &'a Pile,
+    appearance: Option<CardAppearance>,
+    max_cards: usize,
+    theme: Theme,
+}
+
+impl<'a> SpreadPileWidget<'a> {
+    pub fn new(
+        pile: &'a Pile,
+        appearance: Option<CardAppearance>,
+        max_cards: usize,
+        theme: Theme,
+    ) -> Self {
+        Self {
+            pile,
+            appearance,
+            max_cards,
+            theme,
+        }
+    }
+
+    fn spread_width() -> u16 {
+        // Enough to show the suit and rank of covered cards.
+        4
+    }
+
+    pub fn get_width(&self) -> u16 {
+        CardWidget::width() + Self::spread_width() * (self.max_cards.max(1) as u16 - 1)
+    }
+
+    pub fn get_height(&self) -> u16 {
+        CardWidget::height()
+    }
+}
+
+impl<'a> Widget for SpreadPileWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.pile.is_empty() {
+            let widget = EmptySlotWidget::new(self.appearance, self.theme);
+            widget.render(area, buf);
+            return;
+        }
+
+        let num_shown = self.pile.len().min(self.max_cards);
+        let mut region = area;
+
+        for (index, card) in self.pile[self.pile.len() - num_shown..].iter().enumerate() {
+            // Only the top card can be played, so only it gets the appearance.
+            let is_last = index == num_shown - 1;
+            let appearance = is_last.then_some(self.appearance).flatten();
+            let widget = CardWidget::new(*card, appearance, self.theme);
+            widget.render(region, buf);
+
+            region.x += Self::spread_width();
+        }
+    }
+}